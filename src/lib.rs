@@ -1,7 +1,35 @@
 //! ExtString is an attempt to bring string functions from other programming languages to the Rust std String struct
+//!
+//! The crate is `no_std` compatible: disable the default `std` feature to only depend on `alloc`.
+//! Methods relying on hash sets, such as `distinct_char_count` or `unique_slug`, are only available with `std`.
+//!
+//! Grapheme handling comes from the default `unicode` feature. Without it, `reverse` and the other
+//! grapheme-based methods treat every char as one grapheme, which is only correct for text without
+//! combining marks or emoji sequences, and the word and sentence boundary methods are unavailable.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[macro_use]
+extern crate alloc;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "unicode")]
 extern crate unicode_segmentation;
+extern crate unicode_width;
 
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "unicode")]
 use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 /// The trait that adds functionality to the String struct.
 pub trait ExtString {
     /// Reverses order of characters
@@ -27,13 +55,494 @@ pub trait ExtString {
     fn is_alphanumeric(&self) -> bool;
     /// Swaps upper case characters to lower case and vice versa.
     fn swap_case(&self) -> String;
+
+    /// Returns the byte indices at which a soft line break is allowed, based on Unicode word boundaries.
+    /// A break opportunity sits at the start of every segment that follows whitespace, so words are never split.
+    #[cfg(feature = "unicode")]
+    fn word_break_indices(&self) -> Vec<usize>;
+
+    /// Fully justifies a single line by distributing extra spaces between words until its display width is 'width' columns,
+    /// where wide characters such as CJK take two columns.
+    /// Uneven spaces go to the leftmost gaps. Single-word lines, or lines already at least 'width' long,
+    /// are left-justified with single spaces between words.
+    fn justify(&self, width: usize) -> String;
+
+    /// Word-wraps the text to 'width' columns of display width and fully justifies every line except the last one of each paragraph.
+    /// Paragraphs are separated by blank lines. Words longer than 'width' are left on their own line.
+    fn justify_paragraph(&self, width: usize) -> String;
+
+    /// Counts letters, digits, whitespace, punctuation and other characters in a single pass.
+    fn count_by_class(&self) -> CharClassCounts;
+
+    /// Truncates the string to at most 'max_len' characters, ellipsis included, without cutting words in the middle.
+    /// The cut backs up to the previous word boundary and "…" is appended. If the first word is already too long,
+    /// it is cut at 'max_len - 1' characters instead. A string that fits is returned as a simple clone.
+    fn truncate_at_word(&self, max_len: usize) -> String;
+
+    /// Checks if 'needle' is contained in the string, ignoring case.
+    /// Characters are compared lowercased one by one, so "İ" matches "i̇" but not a plain "i", whichever string it is in.
+    fn contains_ci(&self, needle: &str) -> bool;
+
+    /// Replaces all occurrences of 'needle', ignoring case, with the literal 'replacement'.
+    /// Text around the matches is preserved exactly. An empty needle returns a simple cloned string.
+    fn replace_ci(&self, needle: &str, replacement: &str) -> String;
+
+    /// Checks if the string starts with 'prefix', ignoring case with full Unicode lowercasing.
+    fn starts_with_ci(&self, prefix: &str) -> bool;
+    /// Checks if the string ends with 'suffix', ignoring case with full Unicode lowercasing.
+    fn ends_with_ci(&self, suffix: &str) -> bool;
+
+    /// Counts the number of unique characters in a string.
+    #[cfg(feature = "std")]
+    fn distinct_char_count(&self) -> usize;
+
+    /// Checks if any character appears more than once in a string.
+    #[cfg(feature = "std")]
+    fn has_duplicate_chars(&self) -> bool;
+
+    /// Checks if the string contains every letter of the English alphabet at least once, ignoring case.
+    #[cfg(feature = "std")]
+    fn is_pangram(&self) -> bool;
+    /// Checks if the string contains every character of 'alphabet' at least once, ignoring case.
+    #[cfg(feature = "std")]
+    fn is_pangram_for(&self, alphabet: &[char]) -> bool;
+
+    /// Returns the English alphabet letters that are absent from the string, ignoring case, in alphabetical order.
+    #[cfg(feature = "std")]
+    fn missing_letters(&self) -> Vec<char>;
+
+    /// Splits the string into runs of consecutive characters of the same class,
+    /// returning each run's class together with its text.
+    fn class_runs(&self) -> Vec<(CharClass, String)>;
+
+    /// Splits the string at transitions between letters and digits, so "abc123def" becomes ["abc", "123", "def"].
+    /// Characters that are neither letters nor digits act as separators and are dropped.
+    fn split_alpha_numeric(&self) -> Vec<String>;
+
+    /// Compares two dotted version strings component by component, so "1.10.0" is greater than "1.9.0".
+    /// Numeric components are compared as numbers, missing components count as 0,
+    /// and any other component is compared lexically.
+    fn version_cmp(&self, other: &str) -> Ordering;
+
+    /// Replaces every occurrence of any of the literal 'patterns' with 'replacement' in a single pass.
+    /// When several patterns match at the same position, the longest one wins. Empty patterns are ignored.
+    fn redact(&self, patterns: &[&str], replacement: &str) -> String;
+
+    /// Masks the local part of an email address with '*', except for its first and last character,
+    /// so "john.doe@example.com" becomes "j******e@example.com". The domain is left intact.
+    /// Local parts of one or two characters keep only their first character, or none for a single character.
+    /// Strings that do not look like an email address are returned as a simple cloned string.
+    fn mask_email(&self) -> String;
+
+    /// Turns a file name into a display title, so "my_cool_file.txt" becomes "My Cool File".
+    /// Leading path components and the extension are dropped, underscores and hyphens become spaces
+    /// and every word is title-cased.
+    fn humanize_filename(&self) -> String;
+
+    /// Produces a canonical form of the string for case and accent insensitive lookups:
+    /// lowercased, with accents stripped from Latin letters and whitespace collapsed to single spaces.
+    fn fold_for_search(&self) -> String;
+
+    /// Returns every emoji found in the string, in order. Each emoji is a whole grapheme cluster,
+    /// so ZWJ sequences, skin tone modifiers and flags are returned as single entries.
+    fn emoji_list(&self) -> Vec<String>;
+
+    /// Converts full-width ASCII variants (U+FF01 to U+FF5E) and the ideographic space to their half-width equivalents.
+    fn to_halfwidth(&self) -> String;
+    /// Converts printable ASCII characters and the space to their full-width variants.
+    fn to_fullwidth(&self) -> String;
+
+    /// Converts hiragana characters to the matching katakana, leaving all other characters untouched.
+    fn hiragana_to_katakana(&self) -> String;
+    /// Converts katakana characters to the matching hiragana, leaving all other characters untouched.
+    fn katakana_to_hiragana(&self) -> String;
+
+    /// Returns the byte start and end offsets of each sentence, based on Unicode sentence boundaries.
+    /// Surrounding whitespace is excluded from the spans, and every offset lies on a char boundary,
+    /// so `&s[start..end]` slices back to the sentence.
+    #[cfg(feature = "unicode")]
+    fn sentence_spans(&self) -> Vec<(usize, usize)>;
+
+    /// Returns the byte offsets of every character matching the predicate.
+    fn find_all<F: Fn(char) -> bool>(&self, pred: F) -> Vec<usize>;
+
+    /// Alternates the characters of the string and 'other', starting with the string.
+    /// Once the shorter one is exhausted, the rest of the longer one is appended.
+    fn interleave(&self, other: &str) -> String;
+
+    /// Lays the characters out row by row in a grid 'columns' wide, then reads them back column by column,
+    /// as in a columnar transposition cipher. When the last row is only partially filled, its empty cells
+    /// are skipped rather than padded, so the output has the same characters as the input.
+    /// A 'columns' of 0 returns a simple cloned string.
+    fn transpose(&self, columns: usize) -> String;
+
+    /// Counts the paragraphs of a text, which are separated by one or more blank lines.
+    /// Leading and trailing blank lines do not count as paragraphs.
+    fn paragraph_count(&self) -> usize;
+
+    /// Splits a text into paragraphs on blank lines, trimming the whitespace around each paragraph.
+    /// Single newlines inside a paragraph are preserved.
+    fn paragraphs(&self) -> Vec<String>;
+
+    /// Joins the hard-wrapped lines of each paragraph and wraps them again to 'width' columns of display width.
+    /// Paragraphs stay separated by a blank line. Words longer than 'width' are left on their own line.
+    fn reflow(&self, width: usize) -> String;
+
+    /// Returns the byte range 'start..end' of the string, or None if the range is out of bounds,
+    /// reversed, or does not start and end on char boundaries.
+    fn safe_slice(&self, start: usize, end: usize) -> Option<&str>;
+
+    /// Returns all sliding windows of 'size' characters, so "abcd" with a size of 2 gives ["ab", "bc", "cd"].
+    /// A 'size' of 0 or larger than the character length returns an empty vector.
+    fn char_windows(&self, size: usize) -> Vec<String>;
+
+    /// Cyclically rotates the characters 'n' positions to the left, so "abcdef" rotated by 2 becomes "cdefab".
+    /// 'n' is taken modulo the character length.
+    fn rotate_left(&self, n: usize) -> String;
+    /// Cyclically rotates the characters 'n' positions to the right, so "abcdef" rotated by 2 becomes "efabcd".
+    /// 'n' is taken modulo the character length.
+    fn rotate_right(&self, n: usize) -> String;
+
+    /// Swaps the characters at char indices 'i' and 'j'.
+    /// If either index is out of range, a simple cloned string will be returned.
+    fn swap(&self, i: usize, j: usize) -> String;
+
+    /// Returns every bracket that has no matching partner, together with its byte offset, ordered by offset.
+    /// Round, square and curly brackets are checked and nesting is honored. Quotes are not taken into account.
+    fn unmatched_brackets(&self) -> Vec<(char, usize)>;
+
+    /// Checks that all round, square and curly brackets are properly matched and nested.
+    fn is_balanced(&self) -> bool;
+
+    /// Removes everything from 'marker' to the end of each line, along with the whitespace before it.
+    /// The lines themselves are kept, so a comment on its own line leaves an empty line.
+    /// Markers inside double quoted strings, which may contain backslash escapes, are not treated as comments.
+    /// Single quotes are not recognized, since they are mostly apostrophes in comments and values.
+    fn strip_line_comments(&self, marker: &str) -> String;
+
+    /// Interprets C-style escape sequences: `\n`, `\r`, `\t`, `\0`, `\a`, `\b`, `\f`, `\v`, `\\`, `\"`, `\'`,
+    /// `\xNN` with exactly two hex digits, and `\u{...}` with one to six hex digits.
+    /// Returns an error for unknown or malformed escapes.
+    fn unescape(&self) -> Result<String, UnescapeError>;
+
+    /// Converts special and control characters to C-style escape sequences, giving a safe single line representation.
+    /// Newlines, carriage returns, tabs, backslashes and double quotes get their short escapes,
+    /// other ASCII control characters become `\xNN` and remaining control characters become `\u{...}`.
+    /// So do invisible format characters, such as zero-width spaces, byte order marks and bidi overrides,
+    /// and the line and paragraph separators U+2028 and U+2029.
+    /// The result can be turned back into the original string with `unescape`.
+    fn escape(&self) -> String;
+
+    /// Returns the visual width of the leading indentation of the first line.
+    /// Each space counts as 1 and each tab advances to the next multiple of 'tab_size'.
+    /// A 'tab_size' of 0 is treated as 1.
+    fn indentation_width(&self, tab_size: usize) -> usize;
+
+    /// Inspects the indentation of all non-blank lines and reports whether the text is indented with tabs
+    /// or with spaces. The number of spaces per level is the most common change in indentation between
+    /// consecutive lines, the smaller one on a tie, so a single odd line does not spoil the result.
+    /// Text mixing tabs and spaces, or with no indentation at all, is reported as `IndentStyle::Mixed`.
+    fn detect_indent(&self) -> IndentStyle;
+
+    /// Lowercases the whole string, then capitalizes the first letter of the text and of every sentence,
+    /// where a sentence starts after '.', '!' or '?' followed by whitespace, so "example.com" is left alone.
+    fn sentence_case(&self) -> String;
+
+    /// Removes consecutive duplicate lines, like `uniq`. Line endings of the kept lines are preserved.
+    fn dedup_lines(&self) -> String;
+    /// Removes all duplicate lines regardless of where they are, keeping the first occurrence of each.
+    /// Line endings of the kept lines are preserved.
+    #[cfg(feature = "std")]
+    fn dedup_lines_global(&self) -> String;
+
+    /// Sorts the lines lexicographically. Line endings are preserved like in `sort_lines_by`.
+    fn sort_lines(&self) -> String;
+    /// Sorts the lines with the given comparator, for instance `version_cmp` for a natural order.
+    /// Every line keeps its own "\n" or "\r\n" ending, and a missing trailing newline stays missing.
+    fn sort_lines_by<F: FnMut(&str, &str) -> Ordering>(&self, compare: F) -> String;
+
+    /// Shuffles the order of the lines deterministically, using a pseudo random generator seeded with 'seed'.
+    /// The same seed always gives the same order. Line endings are preserved like in `sort_lines_by`.
+    fn shuffle_lines(&self, seed: u64) -> String;
+
+    /// Prefixes each line with a marker and a space, cycling through 'markers' when there are more lines than markers.
+    /// If 'markers' is empty, a simple cloned string will be returned.
+    fn line_gutter(&self, markers: &[char]) -> String;
+
+    /// Removes a leading byte order mark (U+FEFF). If there is none, a simple cloned string will be returned.
+    fn strip_bom(&self) -> String;
+    /// Checks if the string starts with a byte order mark (U+FEFF).
+    fn has_bom(&self) -> bool;
+
+    /// Checks if the string could be the content of a JSON string literal, without the surrounding quotes:
+    /// no unescaped double quotes or control characters, and only valid escape sequences.
+    fn is_json_string_content(&self) -> bool;
+
+    /// Formats a 10 digit US phone number as "(123) 456-7890", ignoring any existing separators.
+    /// Returns None if the string does not contain exactly 10 digits.
+    fn format_phone_us(&self) -> Option<String>;
+
+    /// Keeps only the ASCII digits of a string, in order.
+    fn digits_only(&self) -> String;
+
+    /// Validates the digits of the string with the Luhn checksum, as used by credit card and IMEI numbers.
+    /// Non-digit characters are ignored. Fewer than two digits are never valid.
+    fn is_valid_luhn(&self) -> bool;
+
+    /// Computes the Luhn check digit that makes the digits of the string valid once appended.
+    /// Non-digit characters are ignored. Returns None if there are no digits.
+    fn luhn_check_digit(&self) -> Option<u8>;
+
+    /// Encodes the UTF-8 bytes of the string as RFC 4648 Base32, with '=' padding.
+    fn to_base32(&self) -> String;
+    /// Decodes RFC 4648 Base32 into a string. Decoding ignores case and accepts missing padding,
+    /// but rejects characters outside the alphabet, impossible lengths and bytes that are not valid UTF-8.
+    /// It is strict otherwise: padding, if any, must fill the last group of 8 characters exactly,
+    /// and the unused bits of the last character must be zero, so every string has a single encoding.
+    #[allow(clippy::wrong_self_convention)]
+    fn from_base32(&self) -> Result<String, DecodeError>;
+
+    /// Encodes the UTF-8 bytes of the string as Bitcoin-style Base58. Leading zero bytes become leading '1's.
+    fn to_base58(&self) -> String;
+    /// Decodes Bitcoin-style Base58 into a string. Leading '1's become leading zero bytes.
+    /// Rejects characters outside the alphabet and bytes that are not valid UTF-8.
+    #[allow(clippy::wrong_self_convention)]
+    fn from_base58(&self) -> Result<String, DecodeError>;
+
+    /// Returns the fraction of characters that are alphabetic, between 0 and 1. An empty string gives 0.
+    fn alpha_ratio(&self) -> f64;
+
+    /// Checks if the ratio of uppercase letters to all cased letters exceeds 'threshold', flagging ALL-CAPS text.
+    /// Letters without case, such as Hebrew or CJK, are not counted.
+    /// Strings with fewer than 4 cased letters are never considered shouting.
+    fn is_shouting(&self, threshold: f64) -> bool;
+
+    /// Splits the string at any of the delimiter characters, skipping empty segments.
+    fn split_on_any(&self, delimiters: &[char]) -> Vec<String>;
+
+    /// Splits the string at the given char indices, returning the consecutive segments.
+    /// There is always one more segment than there are indices. Indices are expected to be sorted and in range;
+    /// an index past the end is clamped to the character length and an index smaller than the one before it
+    /// is clamped to that previous index, both producing empty segments.
+    fn split_at_indices(&self, indices: &[usize]) -> Vec<String>;
+
+    /// Slices a fixed-width record into fields of the given char widths, trimming each field.
+    /// Characters left over after the last width form one more field.
+    /// Fields past the end of a short record are empty.
+    fn parse_fixed_width(&self, widths: &[usize]) -> Vec<String>;
+
+    /// Formats tab separated fields into a fixed-width record, padding each field with spaces on the right
+    /// to its width. Fields longer than their width are cut to the first 'width' characters.
+    /// Missing fields are written as blanks and fields without a matching width are dropped.
+    fn to_fixed_width(&self, widths: &[usize]) -> String;
+
+    /// Expands character ranges such as "a-e" into "abcde", like the shorthand of a character class.
+    /// A backslash makes the next character literal, so "\-" is a plain hyphen. Either end of a range may be escaped,
+    /// so "\--/" expands to "-./". Hyphens at the start or end, and ranges whose end comes before their start,
+    /// are kept as they are.
+    fn expand_ranges(&self) -> String;
+
+    /// Collapses runs of three or more consecutive characters into "start-end" notation, so "abcde" becomes "a-e".
+    /// Shorter runs stay literal. Hyphens and backslashes are escaped with a backslash,
+    /// so `expand_ranges` gives back the original string.
+    fn contract_ranges(&self) -> String;
+
+    /// Returns the longest substring that appears at least twice, occurrences possibly overlapping,
+    /// or an empty string if no character repeats. Runs in quadratic time over the characters.
+    fn longest_repeated_substring(&self) -> String;
+
+    /// Checks if the characters of the string appear in 'other' in the same order, not necessarily next to each other.
+    /// An empty string is a subsequence of anything.
+    fn is_subsequence(&self, other: &str) -> bool;
+
+    /// Scores how well 'query' fuzzy matches the string, ignoring case, or returns None if the characters
+    /// of 'query' do not appear in the string in order. Higher scores are better: matches right after
+    /// the previous match and matches at the start of a word are rewarded, gaps between matches are penalized.
+    fn fuzzy_score(&self, query: &str) -> Option<i32>;
+
+    /// Replaces every ASCII digit with 'replacement', keeping the structure of the string,
+    /// so "id-12345" becomes "id-#####".
+    fn redact_digits(&self, replacement: char) -> String;
+
+    /// Splits the string into lowercase search tokens at whitespace, punctuation and camelCase boundaries,
+    /// so "getHTTPResponse-now" gives ["get", "http", "response", "now"].
+    fn search_tokens(&self) -> Vec<String>;
+    /// Same as `search_tokens`, but every token is only kept the first time it appears.
+    #[cfg(feature = "std")]
+    fn search_tokens_unique(&self) -> Vec<String>;
+
+    /// Mirrors every line horizontally: the line is reversed and characters with a mirror image,
+    /// such as '(' and ')', '[' and ']', '{' and '}', '<' and '>', '/' and '\', are swapped. Line endings are preserved.
+    fn mirror(&self) -> String;
+
+    /// Stacks the text vertically, one character per line. Each input line becomes a column,
+    /// and columns are separated by a space. Shorter lines are padded with spaces and trailing spaces are trimmed.
+    fn vertical(&self) -> String;
+
+    /// Renders the string as a 5 row block-letter banner made of '#' characters, using a built-in font
+    /// for A to Z (ignoring case), 0 to 9 and space. Unsupported characters render as blanks.
+    /// Every glyph is 5 columns wide and glyphs are separated by a single blank column.
+    fn banner(&self) -> String;
+
+    /// Counts how many times a character appears in a string.
+    fn count_char(&self, c: char) -> usize;
+
+    /// Returns the char index of the first position where the string and 'other' differ, or None if they are equal.
+    /// If one is a prefix of the other, the character length of the shorter one is returned.
+    fn first_difference(&self, other: &str) -> Option<usize>;
+
+    /// Replaces typographic punctuation from word processors with plain ASCII: curly double quotes become '"',
+    /// curly single quotes and apostrophes become '\'', an em dash becomes "--", an en dash becomes '-'
+    /// and an ellipsis becomes "...".
+    fn strip_smart_quotes(&self) -> String;
+
+    /// Converts straight quotes into typographic curly quotes and "--" into an em dash.
+    /// A quote at the start of the text or after whitespace or an opening bracket opens, any other quote closes,
+    /// so apostrophes in contractions become '’'.
+    fn to_smart_quotes(&self) -> String;
+
+    /// Returns the whitespace delimited word containing the character at 'char_index',
+    /// or None if that character is whitespace or the index is out of range.
+    fn word_at(&self, char_index: usize) -> Option<&str>;
+
+    /// Replaces the characters in the char index range 'start..end' with 'replacement'.
+    /// Offsets past the end are clamped to the character length, and an 'end' before 'start' is clamped to 'start',
+    /// so an empty range inserts 'replacement'.
+    /// Since `String` has an inherent `replace_range`, call this one as `ExtString::replace_range(&s, start, end, replacement)`.
+    fn replace_range(&self, start: usize, end: usize, replacement: &str) -> String;
+
+    /// Applies 'f' to every line, without its line ending, and puts the line's original "\n" or "\r\n" ending
+    /// back after the result. A missing trailing newline stays missing.
+    fn map_lines<F: FnMut(&str) -> String>(&self, f: F) -> String;
+    /// Same as `map_lines`, but the lines are transformed in parallel on the rayon thread pool.
+    /// The output keeps the original line order, so it is always identical to `map_lines`.
+    #[cfg(feature = "rayon")]
+    fn par_map_lines<F: Fn(&str) -> String + Send + Sync>(&self, f: F) -> String;
+
+    /// Checks if two strings are equal once all whitespace is removed from both,
+    /// so "fn main() {}" equals "fn main(){ }" but not "fnmain(){x}".
+    fn eq_ignore_whitespace(&self, other: &str) -> bool;
+
+    /// Maps the string to a bucket in 0..num_buckets using the 64-bit FNV-1a hash of its bytes.
+    /// The result only depends on the string itself, so it is stable across runs and platforms.
+    /// If num_buckets is 0, 0 will be returned.
+    fn bucket(&self, num_buckets: usize) -> usize;
+
+    /// Title-cases every word, except for words found in 'exceptions' (compared case-insensitively),
+    /// which are lowercased instead. The first word is always title-cased. Whitespace is preserved.
+    /// Punctuation around a word is ignored, so "(of)" is an exception and "\"hello\"" becomes "\"Hello\"".
+    fn title_case_except(&self, exceptions: &[&str]) -> String;
+
+    /// Counts the whitespace separated words, as configured by 'opts'.
+    /// Punctuation around a word is ignored, and tokens without any letter or digit are never counted.
+    /// Punctuation inside a token keeps it a single word, so "and/or", "e.g." and "foo@bar.com" count once.
+    fn word_count_opts(&self, opts: WordCountOptions) -> usize;
+
+    /// Removes zero-width characters: zero-width space, non-joiner and joiner, byte order marks and word joiners.
+    /// Removing the zero-width joiner breaks emoji sequences such as "👨‍👩‍👧" into separate emoji,
+    /// use `strip_zero_width_keep_emoji` to keep those intact.
+    fn strip_zero_width(&self) -> String;
+    /// Same as `strip_zero_width`, but keeps a zero-width joiner that sits between two emoji.
+    fn strip_zero_width_keep_emoji(&self) -> String;
+
+    /// Heuristically checks for mojibake, i.e. UTF-8 text that was wrongly decoded as Latin-1 or Windows-1252,
+    /// such as "cafÃ©" or "itâ€™s". It looks for chars whose single byte encodings form a valid multi-byte
+    /// UTF-8 sequence. Legitimate text can trigger it too, e.g. "Ã" followed by "©", so treat it as a hint.
+    fn has_mojibake(&self) -> bool;
+
+    /// Repairs mojibake by encoding every char back to its Latin-1 or Windows-1252 byte and decoding the bytes as UTF-8,
+    /// so "cafÃ©" becomes "café". If a char has no single byte encoding or the bytes are not valid UTF-8,
+    /// a simple cloned string will be returned.
+    fn fix_mojibake(&self) -> String;
+
+    /// Measures the byte length, char count, grapheme count, display width and line count of the string.
+    fn size_report(&self) -> SizeReport;
+
+    /// Truncates the string to at most 'max_bytes' bytes of UTF-8, never splitting a char, and appends "…" when truncated.
+    /// The ellipsis takes 3 bytes of the budget. If 'max_bytes' is too small to hold it, the string is cut without one.
+    /// If the string already fits, a simple cloned string will be returned.
+    fn truncate_bytes_ellipsis(&self, max_bytes: usize) -> String;
+
+    /// Adds exactly 'left' copies of 'c' before the string and 'right' copies after it, whatever its length.
+    fn pad(&self, left: usize, right: usize, c: char) -> String;
+
+    /// Splits a trailing run of ASCII digits off the string and parses it, so "item42" gives ("item", Some(42)).
+    /// Without a numeric suffix, or if it does not fit in a u64, the whole string and None are returned.
+    fn strip_numeric_suffix(&self) -> (String, Option<u64>);
+
+    /// Increments the trailing number of the string, or appends "2" if there is none, so "file" becomes "file2"
+    /// and "file2" becomes "file3". Zero padding is kept where it fits, so "img009" becomes "img010".
+    /// Suffixes of any length are supported, "99" simply grows to "100".
+    fn increment(&self) -> String;
+
+    /// Splits the string at any of the delimiter characters like `split_on_any`,
+    /// but keeps every delimiter as a token of its own, so "a+b" gives ["a", "+", "b"].
+    fn split_on_any_keep(&self, delimiters: &[char]) -> Vec<String>;
+
+    /// Checks that every single or double quote is closed. Inside a quoted part, the other kind of quote
+    /// is ordinary text and a backslash escapes the next character, so "'a\'b'" is balanced.
+    fn has_balanced_quotes(&self) -> bool;
+
+    /// Word-wraps the text to lines of at most 'width' columns of display width, joined with '\n'.
+    /// A word longer than 'width' is broken across lines, each full part ending in a hyphen
+    /// that counts toward the width. With a width below 2 long words are left whole.
+    /// Every part holds at least one character, so a wide character can overflow a very narrow width.
+    fn word_wrap_hyphenate(&self, width: usize) -> String;
+
+    /// Capitalizes the first letter of the text and of every sentence, where a sentence starts after
+    /// '.', '!' or '?' followed by whitespace. Unlike `sentence_case`, all other characters keep their case.
+    fn capitalize_sentences(&self) -> String;
+
+    /// Collapses every run of the same punctuation character into a single one, so "what???" becomes "what?".
+    /// Note that this also turns "..." into ".", use `squeeze_punctuation_to` to allow longer runs.
+    fn squeeze_punctuation(&self) -> String;
+    /// Shortens every run of the same punctuation character to at most 'max_run' characters.
+    /// A 'max_run' of 0 is treated as 1.
+    fn squeeze_punctuation_to(&self, max_run: usize) -> String;
+
+    /// Truncates an HTML fragment to at most 'max_text_len' visible characters, keeping its markup valid.
+    /// Tags do not count toward the length, an entity such as "&amp;" counts as one character,
+    /// and any tag still open at the cut point is closed. Void elements such as `<br>` need no closing tag.
+    fn truncate_html(&self, max_text_len: usize) -> String;
+
+    /// Counts the visible characters of an HTML fragment: tags are ignored and every entity counts as one character.
+    /// This is the length `truncate_html` measures against.
+    fn visible_text_len(&self) -> usize;
+
+    /// Converts an HTML fragment to readable plain text. Tags are removed, entities decoded
+    /// and runs of whitespace collapsed to a single space. `<br>` gives a line break, `<div>`, `<li>`, `<tr>`,
+    /// lists and headings start a new line and `<p>` a new paragraph. The content of `<script>` and `<style>` is dropped.
+    fn html_to_text(&self) -> String;
+
+    /// Computes the Levenshtein distance to 'other': the number of single character insertions,
+    /// deletions and substitutions needed to turn one into the other.
+    fn levenshtein(&self, other: &str) -> usize;
+    /// Computes the Levenshtein distance to 'other' if it is at most 'max', otherwise returns None.
+    /// Only the cells within 'max' of the diagonal are computed and it stops as soon as a whole row exceeds 'max',
+    /// which makes it much faster than `levenshtein` for filtering by a threshold.
+    fn levenshtein_within(&self, other: &str, max: usize) -> Option<usize>;
+
+    /// Computes the Damerau-Levenshtein distance to 'other', which also counts swapping two adjacent characters
+    /// as a single edit, so "ca" and "ac" are 1 apart instead of 2. This is the optimal string alignment variant:
+    /// a substring is never edited again once it has been transposed.
+    fn damerau_levenshtein(&self, other: &str) -> usize;
+
+    /// Pads a decimal number so that decimal points line up in a column: the integer part, sign included,
+    /// is left-padded with spaces to 'int_width' and the fraction is right-padded with zeros to 'frac_width'.
+    /// A missing decimal point is added unless 'frac_width' is 0. Longer parts are never cut.
+    /// If the string is not a decimal number, a simple cloned string will be returned.
+    fn align_decimal(&self, int_width: usize, frac_width: usize) -> String;
+
+    /// Removes the leading whitespace that all non-blank lines have in common, so relative indentation is kept.
+    /// Blank lines do not count toward the common indentation and lose as much of it as they have.
+    /// Tabs and spaces are compared literally. Line endings are preserved.
+    fn dedent_min(&self) -> String;
 }
 
 impl ExtString for String {
     /// Reverses order of characters
     fn reverse(&self) -> String {
-        let mut g: Vec<&str> =
-            UnicodeSegmentation::graphemes(self.as_str(), true).collect::<Vec<&str>>();
+        let mut g: Vec<&str> = graphemes(self).collect::<Vec<&str>>();
         g.reverse();
         g.join("")
     }
@@ -104,7 +613,7 @@ impl ExtString for String {
 
     /// Checks that all characters in a string are numeric characters.
     fn is_numeric(&self) -> bool {
-        let f = |c: char| c.is_numeric();        
+        let f = |c: char| c.is_numeric();
         (!self.is_empty()) && self.chars().all(f)
     }
 
@@ -133,89 +642,4838 @@ impl ExtString for String {
         }
         s
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::ExtString;
+    #[cfg(feature = "unicode")]
+    fn word_break_indices(&self) -> Vec<usize> {
+        let mut indices = Vec::new();
+        let mut after_whitespace = false;
+        for (index, segment) in self.split_word_bound_indices() {
+            if after_whitespace {
+                indices.push(index);
+            }
+            after_whitespace = segment.chars().all(char::is_whitespace);
+        }
+        indices
+    }
 
-    #[test]
-    fn test_reverse() {
-        let original = String::from("123456789");
-        assert_eq!(original.reverse(), "987654321");
-        let chinese = String::from("汉字漢字");
-        assert_eq!(chinese.reverse(), "字漢字汉");
-        let mangled = String::from("גבאabc1汉字漢字");
-        assert_eq!(mangled.reverse(), "字漢字汉1cbaאבג");
-        let weird = String::from("नमस्ते्");
-        assert_eq!(weird.reverse(), "ते्स्मन");
+    fn justify(&self, width: usize) -> String {
+        let words: Vec<&str> = self.split_whitespace().collect();
+        let gaps = words.len().saturating_sub(1);
+        let text_len: usize = words.iter().map(|w| UnicodeWidthStr::width(*w)).sum();
+        if gaps == 0 || text_len + gaps >= width {
+            return words.join(" ");
+        }
+        let spaces = width - text_len;
+        let mut s = String::with_capacity(self.len() + spaces);
+        for (index, word) in words.iter().enumerate() {
+            s.push_str(word);
+            if index < gaps {
+                let extra = if index < spaces % gaps { 1 } else { 0 };
+                for _ in 0..(spaces / gaps + extra) {
+                    s.push(' ');
+                }
+            }
+        }
+        s
     }
 
-    #[test]
-    fn test_pad_left() {
-        let s = "12345";
-        let space = ' ';
-        assert_eq!("12345", String::from(s).pad_left(3, space));
-        assert_eq!("     12345", String::from(s).pad_left(10, space));
+    fn justify_paragraph(&self, width: usize) -> String {
+        let mut paragraphs = Vec::new();
+        for lines in paragraph_lines(self) {
+            let text = lines.join(" ");
+            let wrapped = wrap_words(&text, width, false);
+            let last = wrapped.len() - 1;
+            let justified: Vec<String> = wrapped
+                .into_iter()
+                .enumerate()
+                .map(|(index, line)| {
+                    if index < last {
+                        line.justify(width)
+                    } else {
+                        line
+                    }
+                })
+                .collect();
+            paragraphs.push(justified.join("\n"));
+        }
+        paragraphs.join("\n\n")
     }
 
-    #[test]
-    fn test_pad_right() {
-        let s = "12345";
-        let space = ' ';
-        assert_eq!("12345", String::from(s).pad_right(3, space));
-        assert_eq!("12345     ", String::from(s).pad_right(10, space));
+    fn count_by_class(&self) -> CharClassCounts {
+        let mut counts = CharClassCounts::default();
+        for c in self.chars() {
+            match CharClass::of(c) {
+                CharClass::Letter => counts.letters += 1,
+                CharClass::Digit => counts.digits += 1,
+                CharClass::Whitespace => counts.whitespace += 1,
+                CharClass::Punctuation => counts.punctuation += 1,
+                CharClass::Other => counts.other += 1,
+            }
+        }
+        counts
     }
 
-    #[test]
-    fn test_pad_left_str() {
-        let s = "12345";
-        let padding = "qwerty";
-        assert_eq!("qwerty12345", String::from(s).pad_left_str(11, padding));
-        assert_eq!("qwertyqwe12345", String::from(s).pad_left_str(14, padding));
+    fn truncate_at_word(&self, max_len: usize) -> String {
+        let chars: Vec<char> = self.chars().collect();
+        if chars.len() <= max_len {
+            return self.clone();
+        }
+        if max_len == 0 {
+            return String::new();
+        }
+        let mut cut = max_len - 1;
+        if !chars[cut].is_whitespace() {
+            if let Some(space) = chars[..cut].iter().rposition(|c| c.is_whitespace()) {
+                cut = space;
+            }
+        }
+        let mut s: String = chars[..cut].iter().collect();
+        s.truncate(s.trim_end().len());
+        s.push('…');
+        s
     }
 
-    #[test]
-    fn test_pad_right_str() {
-        let s = "12345";
-        let padding = "qwerty";
-        assert_eq!("12345qwerty", String::from(s).pad_right_str(11, padding));
-        assert_eq!("12345qwertyqwe", String::from(s).pad_right_str(14, padding));
+    fn contains_ci(&self, needle: &str) -> bool {
+        let needle: Vec<char> = needle.chars().flat_map(char::to_lowercase).collect();
+        needle.is_empty()
+            || self
+                .char_indices()
+                .any(|(index, _)| prefix_len_ci(&self[index..], &needle).is_some())
     }
 
-    #[test]
-    fn test_is_numeric() {
-        assert!(String::from("123456").is_numeric());
-        assert!(String::from("000100").is_numeric());
-        assert!(!String::from("123v56").is_numeric());
-        assert!(!String::from("-123v56").is_numeric());
+    fn replace_ci(&self, needle: &str, replacement: &str) -> String {
+        if needle.is_empty() {
+            return self.clone();
+        }
+        let needle: Vec<char> = needle.chars().flat_map(char::to_lowercase).collect();
+        let mut s = String::with_capacity(self.len());
+        let mut rest = self.as_str();
+        while let Some(c) = rest.chars().next() {
+            match prefix_len_ci(rest, &needle) {
+                Some(len) => {
+                    s.push_str(replacement);
+                    rest = &rest[len..];
+                }
+                None => {
+                    s.push(c);
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+        s
     }
 
-    #[test]
-    fn test_is_alphabetic() {
-        assert!(String::from("abcאבג").is_alphabetic());
-        assert!(String::from("literal").is_alphabetic());
-        assert!(!String::from("v1234").is_alphabetic());
-        assert!(!String::from("6v7777").is_alphabetic());
+    fn starts_with_ci(&self, prefix: &str) -> bool {
+        let prefix: Vec<char> = prefix.chars().flat_map(char::to_lowercase).collect();
+        prefix.is_empty() || prefix_len_ci(self, &prefix).is_some()
     }
 
-    #[test]
-    fn test_is_alphanumeric() {
-        assert!(String::from("ab123cאבג").is_alphanumeric());
-        assert!(String::from("5yu32bliteral").is_alphanumeric());
-        assert!(!String::from("!@567").is_alphanumeric());
-        assert!(!String::from("<(*^*)>").is_alphanumeric());
+    fn ends_with_ci(&self, suffix: &str) -> bool {
+        let suffix: Vec<char> = suffix.chars().flat_map(char::to_lowercase).collect();
+        if suffix.is_empty() {
+            return true;
+        }
+        self.char_indices()
+            .rev()
+            .any(|(index, _)| prefix_len_ci(&self[index..], &suffix) == Some(self.len() - index))
     }
 
-    #[test]
-    fn test_is_swap_case() {
-        let s1 = String::from("One Two Three");
-        assert_eq!("oNE tWO tHREE", s1.swap_case());
+    #[cfg(feature = "std")]
+    fn distinct_char_count(&self) -> usize {
+        self.chars().collect::<HashSet<char>>().len()
+    }
 
-        let s2 = String::from("Y SO SERIOUS???");
-        assert_eq!("y so serious???", s2.swap_case());
+    #[cfg(feature = "std")]
+    fn has_duplicate_chars(&self) -> bool {
+        let mut seen = HashSet::new();
+        !self.chars().all(|c| seen.insert(c))
+    }
 
-        let s3 = String::from("משהו בעברית");
-        assert_eq!("משהו בעברית", s3.swap_case());
+    #[cfg(feature = "std")]
+    fn is_pangram(&self) -> bool {
+        self.is_pangram_for(&ENGLISH_ALPHABET)
+    }
+
+    #[cfg(feature = "std")]
+    fn is_pangram_for(&self, alphabet: &[char]) -> bool {
+        let present: HashSet<char> = self.chars().flat_map(char::to_lowercase).collect();
+        alphabet
+            .iter()
+            .all(|c| c.to_lowercase().all(|lower| present.contains(&lower)))
+    }
+
+    #[cfg(feature = "std")]
+    fn missing_letters(&self) -> Vec<char> {
+        let present: HashSet<char> = self.chars().flat_map(char::to_lowercase).collect();
+        ENGLISH_ALPHABET
+            .iter()
+            .filter(|c| !present.contains(c))
+            .cloned()
+            .collect()
+    }
+
+    fn class_runs(&self) -> Vec<(CharClass, String)> {
+        let mut runs: Vec<(CharClass, String)> = Vec::new();
+        for c in self.chars() {
+            let class = CharClass::of(c);
+            match runs.last_mut() {
+                Some((last, run)) if *last == class => run.push(c),
+                _ => runs.push((class, c.to_string())),
+            }
+        }
+        runs
+    }
+
+    fn split_alpha_numeric(&self) -> Vec<String> {
+        self.class_runs()
+            .into_iter()
+            .filter(|(class, _)| *class == CharClass::Letter || *class == CharClass::Digit)
+            .map(|(_, run)| run)
+            .collect()
+    }
+
+    fn version_cmp(&self, other: &str) -> Ordering {
+        let mut left = self.split('.');
+        let mut right = other.split('.');
+        loop {
+            let (a, b) = match (left.next(), right.next()) {
+                (None, None) => return Ordering::Equal,
+                (a, b) => (a.unwrap_or("0"), b.unwrap_or("0")),
+            };
+            let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                _ => a.cmp(b),
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+    }
+
+    fn redact(&self, patterns: &[&str], replacement: &str) -> String {
+        let mut s = String::with_capacity(self.len());
+        let mut rest = self.as_str();
+        while let Some(c) = rest.chars().next() {
+            let longest = patterns
+                .iter()
+                .filter(|p| !p.is_empty() && rest.starts_with(*p))
+                .map(|p| p.len())
+                .max();
+            match longest {
+                Some(len) => {
+                    s.push_str(replacement);
+                    rest = &rest[len..];
+                }
+                None => {
+                    s.push(c);
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+        s
+    }
+
+    fn mask_email(&self) -> String {
+        let (local, domain) = match self.split_once('@') {
+            Some(parts) => parts,
+            None => return self.clone(),
+        };
+        if local.is_empty()
+            || domain.contains('@')
+            || !domain.contains('.')
+            || domain.starts_with('.')
+            || domain.ends_with('.')
+            || self.chars().any(char::is_whitespace)
+        {
+            return self.clone();
+        }
+        let count = local.chars().count();
+        let mut s = String::with_capacity(self.len());
+        for (index, c) in local.chars().enumerate() {
+            if (index == 0 && count > 1) || (index == count - 1 && count > 2) {
+                s.push(c);
+            } else {
+                s.push('*');
+            }
+        }
+        s.push('@');
+        s.push_str(domain);
+        s
+    }
+
+    fn humanize_filename(&self) -> String {
+        let name = self.rsplit(['/', '\\']).next().unwrap_or("");
+        let stem = match name.rfind('.') {
+            Some(index) if index > 0 => &name[..index],
+            _ => name,
+        };
+        let words: Vec<String> = stem
+            .split(|c: char| c == '_' || c == '-' || c.is_whitespace())
+            .filter(|w| !w.is_empty())
+            .map(|w| {
+                let mut chars = w.chars();
+                let first = chars.next().unwrap();
+                first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect()
+            })
+            .collect();
+        words.join(" ")
+    }
+
+    fn fold_for_search(&self) -> String {
+        let mut folded = String::with_capacity(self.len());
+        for c in self.to_lowercase().chars() {
+            match fold_accent(c) {
+                Some(base) => folded.push_str(base),
+                None => folded.push(c),
+            }
+        }
+        folded.split_whitespace().collect::<Vec<&str>>().join(" ")
+    }
+
+    fn emoji_list(&self) -> Vec<String> {
+        graphemes(self)
+            .filter(|g| is_emoji_grapheme(g))
+            .map(String::from)
+            .collect()
+    }
+
+    fn to_halfwidth(&self) -> String {
+        self.chars()
+            .map(|c| match c {
+                '\u{3000}' => ' ',
+                '\u{FF01}'..='\u{FF5E}' => {
+                    core::char::from_u32(c as u32 - FULLWIDTH_OFFSET).unwrap()
+                }
+                _ => c,
+            })
+            .collect()
+    }
+
+    fn to_fullwidth(&self) -> String {
+        self.chars()
+            .map(|c| match c {
+                ' ' => '\u{3000}',
+                '!'..='~' => core::char::from_u32(c as u32 + FULLWIDTH_OFFSET).unwrap(),
+                _ => c,
+            })
+            .collect()
+    }
+
+    fn hiragana_to_katakana(&self) -> String {
+        self.chars()
+            .map(|c| match c {
+                '\u{3041}'..='\u{3096}' | '\u{309D}'..='\u{309E}' => {
+                    core::char::from_u32(c as u32 + KANA_OFFSET).unwrap()
+                }
+                _ => c,
+            })
+            .collect()
+    }
+
+    fn katakana_to_hiragana(&self) -> String {
+        self.chars()
+            .map(|c| match c {
+                '\u{30A1}'..='\u{30F6}' | '\u{30FD}'..='\u{30FE}' => {
+                    core::char::from_u32(c as u32 - KANA_OFFSET).unwrap()
+                }
+                _ => c,
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "unicode")]
+    fn sentence_spans(&self) -> Vec<(usize, usize)> {
+        self.split_sentence_bound_indices()
+            .filter_map(|(index, sentence)| {
+                let trimmed = sentence.trim_start();
+                let start = index + sentence.len() - trimmed.len();
+                let end = start + trimmed.trim_end().len();
+                if start < end {
+                    Some((start, end))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn find_all<F: Fn(char) -> bool>(&self, pred: F) -> Vec<usize> {
+        self.char_indices()
+            .filter(|&(_, c)| pred(c))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn interleave(&self, other: &str) -> String {
+        let mut s = String::with_capacity(self.len() + other.len());
+        let mut left = self.chars();
+        let mut right = other.chars();
+        loop {
+            match (left.next(), right.next()) {
+                (None, None) => break,
+                (a, b) => {
+                    s.extend(a);
+                    s.extend(b);
+                }
+            }
+        }
+        s
+    }
+
+    fn transpose(&self, columns: usize) -> String {
+        if columns == 0 {
+            return self.clone();
+        }
+        let chars: Vec<char> = self.chars().collect();
+        let mut s = String::with_capacity(self.len());
+        for column in 0..columns {
+            s.extend(chars.iter().skip(column).step_by(columns));
+        }
+        s
+    }
+
+    fn paragraph_count(&self) -> usize {
+        paragraph_lines(self).len()
+    }
+
+    fn paragraphs(&self) -> Vec<String> {
+        paragraph_lines(self)
+            .into_iter()
+            .map(|lines| lines.join("\n").trim().to_string())
+            .collect()
+    }
+
+    fn reflow(&self, width: usize) -> String {
+        let paragraphs: Vec<String> = paragraph_lines(self)
+            .into_iter()
+            .map(|lines| wrap_words(&lines.join(" "), width, false).join("\n"))
+            .collect();
+        paragraphs.join("\n\n")
+    }
+
+    fn safe_slice(&self, start: usize, end: usize) -> Option<&str> {
+        self.get(start..end)
+    }
+
+    fn char_windows(&self, size: usize) -> Vec<String> {
+        if size == 0 {
+            return Vec::new();
+        }
+        let chars: Vec<char> = self.chars().collect();
+        chars.windows(size).map(|w| w.iter().collect()).collect()
+    }
+
+    fn rotate_left(&self, n: usize) -> String {
+        let mut chars: Vec<char> = self.chars().collect();
+        if !chars.is_empty() {
+            let len = chars.len();
+            chars.rotate_left(n % len);
+        }
+        chars.into_iter().collect()
+    }
+
+    fn rotate_right(&self, n: usize) -> String {
+        let mut chars: Vec<char> = self.chars().collect();
+        if !chars.is_empty() {
+            let len = chars.len();
+            chars.rotate_right(n % len);
+        }
+        chars.into_iter().collect()
+    }
+
+    fn swap(&self, i: usize, j: usize) -> String {
+        let mut chars: Vec<char> = self.chars().collect();
+        if i >= chars.len() || j >= chars.len() {
+            return self.clone();
+        }
+        chars.swap(i, j);
+        chars.into_iter().collect()
+    }
+
+    fn unmatched_brackets(&self) -> Vec<(char, usize)> {
+        let mut open: Vec<(char, usize)> = Vec::new();
+        let mut unmatched = Vec::new();
+        for (index, c) in self.char_indices() {
+            let opener = match c {
+                '(' | '[' | '{' => {
+                    open.push((c, index));
+                    continue;
+                }
+                ')' => '(',
+                ']' => '[',
+                '}' => '{',
+                _ => continue,
+            };
+            match open.last() {
+                Some(&(last, _)) if last == opener => {
+                    open.pop();
+                }
+                _ => unmatched.push((c, index)),
+            }
+        }
+        unmatched.extend(open);
+        unmatched.sort_by_key(|&(_, index)| index);
+        unmatched
+    }
+
+    fn is_balanced(&self) -> bool {
+        self.unmatched_brackets().is_empty()
+    }
+
+    fn strip_line_comments(&self, marker: &str) -> String {
+        if marker.is_empty() {
+            return self.clone();
+        }
+        let lines: Vec<String> = self
+            .split('\n')
+            .map(|line| {
+                let (content, cr) = match line.strip_suffix('\r') {
+                    Some(content) => (content, "\r"),
+                    None => (line, ""),
+                };
+                let mut in_quotes = false;
+                let mut escaped = false;
+                for (index, c) in content.char_indices() {
+                    if escaped {
+                        escaped = false;
+                    } else if in_quotes && c == '\\' {
+                        escaped = true;
+                    } else if c == '"' {
+                        in_quotes = !in_quotes;
+                    } else if !in_quotes && content[index..].starts_with(marker) {
+                        return format!("{}{}", content[..index].trim_end(), cr);
+                    }
+                }
+                line.to_string()
+            })
+            .collect();
+        lines.join("\n")
+    }
+
+    fn unescape(&self) -> Result<String, UnescapeError> {
+        let mut s = String::with_capacity(self.len());
+        let mut chars = self.char_indices();
+        while let Some((index, c)) = chars.next() {
+            if c != '\\' {
+                s.push(c);
+                continue;
+            }
+            let escaped = match chars.next() {
+                Some((_, e)) => e,
+                None => return Err(UnescapeError::TrailingBackslash),
+            };
+            let unescaped = match escaped {
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                '0' => '\0',
+                'a' => '\u{07}',
+                'b' => '\u{08}',
+                'f' => '\u{0C}',
+                'v' => '\u{0B}',
+                '\\' | '"' | '\'' => escaped,
+                'x' => {
+                    let digits: String = chars.by_ref().take(2).map(|(_, d)| d).collect();
+                    if digits.len() != 2 || !digits.chars().all(|d| d.is_ascii_hexdigit()) {
+                        return Err(UnescapeError::InvalidEscape(index));
+                    }
+                    let value = u32::from_str_radix(&digits, 16).unwrap();
+                    core::char::from_u32(value).unwrap()
+                }
+                'u' => {
+                    if chars.next().map(|(_, b)| b) != Some('{') {
+                        return Err(UnescapeError::InvalidEscape(index));
+                    }
+                    let mut digits = String::new();
+                    loop {
+                        match chars.next() {
+                            Some((_, '}')) => break,
+                            Some((_, d)) if d.is_ascii_hexdigit() && digits.len() < 6 => {
+                                digits.push(d)
+                            }
+                            _ => return Err(UnescapeError::InvalidEscape(index)),
+                        }
+                    }
+                    if digits.is_empty() {
+                        return Err(UnescapeError::InvalidEscape(index));
+                    }
+                    let value = u32::from_str_radix(&digits, 16).unwrap();
+                    match core::char::from_u32(value) {
+                        Some(u) => u,
+                        None => return Err(UnescapeError::InvalidCodePoint(index)),
+                    }
+                }
+                _ => return Err(UnescapeError::InvalidEscape(index)),
+            };
+            s.push(unescaped);
+        }
+        Ok(s)
+    }
+
+    fn escape(&self) -> String {
+        let mut s = String::with_capacity(self.len());
+        for c in self.chars() {
+            match c {
+                '\n' => s.push_str("\\n"),
+                '\r' => s.push_str("\\r"),
+                '\t' => s.push_str("\\t"),
+                '\\' => s.push_str("\\\\"),
+                '"' => s.push_str("\\\""),
+                c if c.is_ascii_control() => s.push_str(&format!("\\x{:02x}", c as u32)),
+                c if c.is_control() || is_format_or_separator(c) => {
+                    s.push_str(&format!("\\u{{{:x}}}", c as u32))
+                }
+                c => s.push(c),
+            }
+        }
+        s
+    }
+
+    fn indentation_width(&self, tab_size: usize) -> usize {
+        let tab_size = tab_size.max(1);
+        let mut width = 0;
+        for c in self.chars() {
+            match c {
+                ' ' => width += 1,
+                '\t' => width = (width / tab_size + 1) * tab_size,
+                _ => break,
+            }
+        }
+        width
+    }
+
+    fn detect_indent(&self) -> IndentStyle {
+        let mut tabs = false;
+        let mut previous = 0;
+        let mut deltas: Vec<(usize, usize)> = Vec::new();
+        for line in self.lines().filter(|l| !l.trim().is_empty()) {
+            let indent: String = line
+                .chars()
+                .take_while(|&c| c == ' ' || c == '\t')
+                .collect();
+            if indent.chars().all(|c| c == ' ') {
+                let delta = indent.len().abs_diff(previous);
+                previous = indent.len();
+                if delta == 0 {
+                    continue;
+                }
+                match deltas.iter_mut().find(|(d, _)| *d == delta) {
+                    Some((_, count)) => *count += 1,
+                    None => deltas.push((delta, 1)),
+                }
+            } else if indent.chars().all(|c| c == '\t') {
+                tabs = true;
+            } else {
+                return IndentStyle::Mixed;
+            }
+        }
+        let spaces = deltas
+            .iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+            .map_or(0, |&(delta, _)| delta);
+        match (tabs, spaces) {
+            (true, 0) => IndentStyle::Tabs,
+            (false, n) if n > 0 => IndentStyle::Spaces(n),
+            _ => IndentStyle::Mixed,
+        }
+    }
+
+    fn sentence_case(&self) -> String {
+        let mut s = String::with_capacity(self.len());
+        let mut capitalize = true;
+        let mut after_terminator = false;
+        for c in self.to_lowercase().chars() {
+            if capitalize && c.is_alphabetic() {
+                s.extend(c.to_uppercase());
+                capitalize = false;
+            } else {
+                if after_terminator && c.is_whitespace() {
+                    capitalize = true;
+                }
+                s.push(c);
+            }
+            after_terminator = c == '.' || c == '!' || c == '?';
+        }
+        s
+    }
+
+    fn dedup_lines(&self) -> String {
+        let mut s = String::with_capacity(self.len());
+        let mut previous = None;
+        for line in self.split_inclusive('\n') {
+            let content = line_content(line);
+            if previous != Some(content) {
+                s.push_str(line);
+            }
+            previous = Some(content);
+        }
+        s
+    }
+
+    #[cfg(feature = "std")]
+    fn dedup_lines_global(&self) -> String {
+        let mut s = String::with_capacity(self.len());
+        let mut seen = HashSet::new();
+        for line in self.split_inclusive('\n') {
+            if seen.insert(line_content(line)) {
+                s.push_str(line);
+            }
+        }
+        s
+    }
+
+    fn sort_lines(&self) -> String {
+        self.sort_lines_by(|a, b| a.cmp(b))
+    }
+
+    fn sort_lines_by<F: FnMut(&str, &str) -> Ordering>(&self, mut compare: F) -> String {
+        let mut lines = split_line_endings(self);
+        lines.sort_by(|a, b| compare(a.0, b.0));
+        join_line_endings(lines)
+    }
+
+    fn shuffle_lines(&self, seed: u64) -> String {
+        let mut lines = split_line_endings(self);
+        let mut state = seed;
+        for i in (1..lines.len()).rev() {
+            let j = (splitmix64(&mut state) % (i as u64 + 1)) as usize;
+            lines.swap(i, j);
+        }
+        join_line_endings(lines)
+    }
+
+    fn line_gutter(&self, markers: &[char]) -> String {
+        if markers.is_empty() {
+            return self.clone();
+        }
+        let mut s = String::with_capacity(self.len() + 2 * self.lines().count());
+        for (line, marker) in self.split_inclusive('\n').zip(markers.iter().cycle()) {
+            s.push(*marker);
+            s.push(' ');
+            s.push_str(line);
+        }
+        s
+    }
+
+    fn strip_bom(&self) -> String {
+        self.strip_prefix('\u{FEFF}').unwrap_or(self).to_string()
+    }
+
+    fn has_bom(&self) -> bool {
+        self.starts_with('\u{FEFF}')
+    }
+
+    fn is_json_string_content(&self) -> bool {
+        let mut chars = self.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' | '\u{0}'..='\u{1F}' => return false,
+                '\\' => match chars.next() {
+                    Some('"') | Some('\\') | Some('/') | Some('b') | Some('f') | Some('n')
+                    | Some('r') | Some('t') => {}
+                    Some('u') => {
+                        let digits = chars.by_ref().take(4).filter(char::is_ascii_hexdigit);
+                        if digits.count() != 4 {
+                            return false;
+                        }
+                    }
+                    _ => return false,
+                },
+                _ => {}
+            }
+        }
+        true
+    }
+
+    fn format_phone_us(&self) -> Option<String> {
+        let digits = self.digits_only();
+        if digits.len() != 10 {
+            return None;
+        }
+        Some(format!(
+            "({}) {}-{}",
+            &digits[..3],
+            &digits[3..6],
+            &digits[6..]
+        ))
+    }
+
+    fn digits_only(&self) -> String {
+        self.chars().filter(char::is_ascii_digit).collect()
+    }
+
+    fn is_valid_luhn(&self) -> bool {
+        let digits = self.digits_only();
+        digits.len() > 1 && luhn_sum(&digits, false).is_multiple_of(10)
+    }
+
+    fn luhn_check_digit(&self) -> Option<u8> {
+        let digits = self.digits_only();
+        if digits.is_empty() {
+            return None;
+        }
+        Some(((10 - luhn_sum(&digits, true) % 10) % 10) as u8)
+    }
+
+    fn to_base32(&self) -> String {
+        let mut s = String::with_capacity(self.len().div_ceil(5) * 8);
+        for chunk in self.as_bytes().chunks(5) {
+            let mut buffer = [0u8; 5];
+            buffer[..chunk.len()].copy_from_slice(chunk);
+            let bits = buffer
+                .iter()
+                .fold(0u64, |acc, &b| (acc << 8) | u64::from(b));
+            let symbols = (chunk.len() * 8).div_ceil(5);
+            for index in 0..8 {
+                if index < symbols {
+                    let value = (bits >> (35 - index * 5)) & 0x1F;
+                    s.push(BASE32_ALPHABET[value as usize] as char);
+                } else {
+                    s.push('=');
+                }
+            }
+        }
+        s
+    }
+
+    fn from_base32(&self) -> Result<String, DecodeError> {
+        let data = self.trim_end_matches('=');
+        if !matches!(data.len() % 8, 0 | 2 | 4 | 5 | 7) {
+            return Err(DecodeError::InvalidLength);
+        }
+        let padding = self.len() - data.len();
+        if padding > 0 && padding != (8 - data.len() % 8) % 8 {
+            return Err(DecodeError::InvalidPadding);
+        }
+        let mut bytes = Vec::with_capacity(data.len() * 5 / 8);
+        let mut bits = 0u32;
+        let mut bit_count = 0;
+        for c in data.chars() {
+            let upper = c.to_ascii_uppercase();
+            let value = match BASE32_ALPHABET.iter().position(|&b| b as char == upper) {
+                Some(value) => value as u32,
+                None => return Err(DecodeError::InvalidCharacter(c)),
+            };
+            bits = (bits << 5) | value;
+            bit_count += 5;
+            if bit_count >= 8 {
+                bit_count -= 8;
+                bytes.push((bits >> bit_count) as u8);
+                bits &= (1 << bit_count) - 1;
+            }
+        }
+        if bits != 0 {
+            return Err(DecodeError::InvalidPadding);
+        }
+        String::from_utf8(bytes).map_err(|_| DecodeError::InvalidUtf8)
+    }
+
+    fn to_base58(&self) -> String {
+        let bytes = self.as_bytes();
+        let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+        let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+        for &b in &bytes[zeros..] {
+            let mut carry = u32::from(b);
+            for digit in digits.iter_mut() {
+                carry += u32::from(*digit) << 8;
+                *digit = (carry % 58) as u8;
+                carry /= 58;
+            }
+            while carry > 0 {
+                digits.push((carry % 58) as u8);
+                carry /= 58;
+            }
+        }
+        let mut s = String::with_capacity(zeros + digits.len());
+        for _ in 0..zeros {
+            s.push('1');
+        }
+        s.extend(
+            digits
+                .iter()
+                .rev()
+                .map(|&d| BASE58_ALPHABET[d as usize] as char),
+        );
+        s
+    }
+
+    fn from_base58(&self) -> Result<String, DecodeError> {
+        let zeros = self.chars().take_while(|&c| c == '1').count();
+        let mut bytes: Vec<u8> = Vec::with_capacity(self.len());
+        for c in self.chars().skip(zeros) {
+            let mut carry = match BASE58_ALPHABET.iter().position(|&b| b as char == c) {
+                Some(value) => value as u32,
+                None => return Err(DecodeError::InvalidCharacter(c)),
+            };
+            for byte in bytes.iter_mut() {
+                carry += u32::from(*byte) * 58;
+                *byte = carry as u8;
+                carry >>= 8;
+            }
+            while carry > 0 {
+                bytes.push(carry as u8);
+                carry >>= 8;
+            }
+        }
+        bytes.extend(core::iter::repeat_n(0, zeros));
+        bytes.reverse();
+        String::from_utf8(bytes).map_err(|_| DecodeError::InvalidUtf8)
+    }
+
+    fn alpha_ratio(&self) -> f64 {
+        let count = self.chars().count();
+        if count == 0 {
+            return 0.0;
+        }
+        self.chars().filter(|c| c.is_alphabetic()).count() as f64 / count as f64
+    }
+
+    fn is_shouting(&self, threshold: f64) -> bool {
+        let mut upper = 0;
+        let mut cased = 0;
+        for c in self.chars() {
+            if c.is_uppercase() {
+                upper += 1;
+                cased += 1;
+            } else if c.is_lowercase() {
+                cased += 1;
+            }
+        }
+        cased >= SHOUTING_MIN_LETTERS && upper as f64 / cased as f64 > threshold
+    }
+
+    fn split_on_any(&self, delimiters: &[char]) -> Vec<String> {
+        self.split(delimiters)
+            .filter(|segment| !segment.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    fn split_at_indices(&self, indices: &[usize]) -> Vec<String> {
+        let chars: Vec<char> = self.chars().collect();
+        let mut segments = Vec::with_capacity(indices.len() + 1);
+        let mut start = 0;
+        for &index in indices {
+            let end = index.clamp(start, chars.len());
+            segments.push(chars[start..end].iter().collect());
+            start = end;
+        }
+        segments.push(chars[start..].iter().collect());
+        segments
+    }
+
+    fn parse_fixed_width(&self, widths: &[usize]) -> Vec<String> {
+        let indices: Vec<usize> = widths
+            .iter()
+            .scan(0, |end, width| {
+                *end += width;
+                Some(*end)
+            })
+            .collect();
+        let mut fields = self.split_at_indices(&indices);
+        if fields.last().is_some_and(String::is_empty) {
+            fields.pop();
+        }
+        for field in fields.iter_mut() {
+            *field = field.trim().to_string();
+        }
+        fields
+    }
+
+    fn to_fixed_width(&self, widths: &[usize]) -> String {
+        let mut fields = self.split('\t');
+        let mut s = String::with_capacity(widths.iter().sum());
+        for &width in widths {
+            let field: String = fields.next().unwrap_or("").chars().take(width).collect();
+            s.push_str(&field.pad_right(width, ' '));
+        }
+        s
+    }
+
+    fn expand_ranges(&self) -> String {
+        let chars: Vec<char> = self.chars().collect();
+        let unit = |index: usize| match chars.get(index) {
+            Some('\\') if index + 1 < chars.len() => Some((chars[index + 1], index + 2)),
+            Some(&c) => Some((c, index + 1)),
+            None => None,
+        };
+        let mut s = String::with_capacity(self.len());
+        let mut index = 0;
+        while let Some((start, next)) = unit(index) {
+            if chars.get(next) == Some(&'-') {
+                if let Some((end, after)) = unit(next + 1) {
+                    if start <= end {
+                        s.extend(start..=end);
+                        index = after;
+                        continue;
+                    }
+                }
+            }
+            s.push(start);
+            index = next;
+        }
+        s
+    }
+
+    fn contract_ranges(&self) -> String {
+        let chars: Vec<char> = self.chars().collect();
+        let mut s = String::with_capacity(self.len());
+        let push_literal = |s: &mut String, c: char| {
+            if c == '-' || c == '\\' {
+                s.push('\\');
+            }
+            s.push(c);
+        };
+        let mut start = 0;
+        while start < chars.len() {
+            let mut end = start;
+            while end + 1 < chars.len() && chars[end + 1] as u32 == chars[end] as u32 + 1 {
+                end += 1;
+            }
+            if end - start >= 2 {
+                push_literal(&mut s, chars[start]);
+                s.push('-');
+                push_literal(&mut s, chars[end]);
+            } else {
+                for &c in &chars[start..=end] {
+                    push_literal(&mut s, c);
+                }
+            }
+            start = end + 1;
+        }
+        s
+    }
+
+    fn longest_repeated_substring(&self) -> String {
+        let chars: Vec<char> = self.chars().collect();
+        let n = chars.len();
+        // lengths[j] holds the length of the common prefix of the suffixes starting at i and j, for j > i.
+        let mut lengths = vec![0usize; n + 1];
+        let mut best_len = 0;
+        let mut best_start = 0;
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                lengths[j] = if chars[i] == chars[j] {
+                    lengths[j + 1] + 1
+                } else {
+                    0
+                };
+                if lengths[j] >= best_len && lengths[j] > 0 {
+                    best_len = lengths[j];
+                    best_start = i;
+                }
+            }
+        }
+        chars[best_start..best_start + best_len].iter().collect()
+    }
+
+    fn is_subsequence(&self, other: &str) -> bool {
+        let mut haystack = other.chars();
+        self.chars().all(|c| haystack.any(|h| h == c))
+    }
+
+    fn fuzzy_score(&self, query: &str) -> Option<i32> {
+        let chars: Vec<char> = self.chars().collect();
+        let mut score = 0;
+        let mut position = 0;
+        let mut previous_match: Option<usize> = None;
+        for q in query.chars().flat_map(char::to_lowercase) {
+            let found =
+                (position..chars.len()).find(|&i| chars[i].to_lowercase().eq(q.to_lowercase()))?;
+            score += FUZZY_MATCH;
+            match previous_match {
+                Some(previous) if previous + 1 == found => score += FUZZY_CONSECUTIVE,
+                Some(previous) => score -= (found - previous - 1) as i32,
+                None => {}
+            }
+            let word_start = found == 0
+                || !chars[found - 1].is_alphanumeric()
+                || (chars[found - 1].is_lowercase() && chars[found].is_uppercase());
+            if word_start {
+                score += FUZZY_WORD_START;
+            }
+            previous_match = Some(found);
+            position = found + 1;
+        }
+        Some(score)
+    }
+
+    fn redact_digits(&self, replacement: char) -> String {
+        self.chars()
+            .map(|c| if c.is_ascii_digit() { replacement } else { c })
+            .collect()
+    }
+
+    fn search_tokens(&self) -> Vec<String> {
+        let mut tokens = Vec::new();
+        for word in self
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+        {
+            let chars: Vec<char> = word.chars().collect();
+            let mut start = 0;
+            for i in 1..chars.len() {
+                let boundary = chars[i].is_uppercase()
+                    && (!chars[i - 1].is_uppercase()
+                        || chars.get(i + 1).is_some_and(|next| next.is_lowercase()));
+                if boundary {
+                    tokens.push(chars[start..i].iter().collect::<String>().to_lowercase());
+                    start = i;
+                }
+            }
+            tokens.push(chars[start..].iter().collect::<String>().to_lowercase());
+        }
+        tokens
+    }
+
+    #[cfg(feature = "std")]
+    fn search_tokens_unique(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        self.search_tokens()
+            .into_iter()
+            .filter(|token| seen.insert(token.clone()))
+            .collect()
+    }
+
+    fn mirror(&self) -> String {
+        let mut s = String::with_capacity(self.len());
+        for line in self.split_inclusive('\n') {
+            let content = line_content(line);
+            s.extend(graphemes(content).rev().map(|g| match g {
+                "(" => ")",
+                ")" => "(",
+                "[" => "]",
+                "]" => "[",
+                "{" => "}",
+                "}" => "{",
+                "<" => ">",
+                ">" => "<",
+                "/" => "\\",
+                "\\" => "/",
+                _ => g,
+            }));
+            s.push_str(&line[content.len()..]);
+        }
+        s
+    }
+
+    fn vertical(&self) -> String {
+        let columns: Vec<Vec<char>> = self.lines().map(|line| line.chars().collect()).collect();
+        let height = columns.iter().map(Vec::len).max().unwrap_or(0);
+        let mut rows = Vec::with_capacity(height);
+        for index in 0..height {
+            let cells: Vec<String> = columns
+                .iter()
+                .map(|column| column.get(index).cloned().unwrap_or(' ').to_string())
+                .collect();
+            rows.push(cells.join(" ").trim_end().to_string());
+        }
+        rows.join("\n")
+    }
+
+    fn banner(&self) -> String {
+        let glyphs: Vec<[&str; 5]> = self.chars().map(banner_glyph).collect();
+        let rows: Vec<String> = (0..5)
+            .map(|row| {
+                glyphs
+                    .iter()
+                    .map(|glyph| glyph[row])
+                    .collect::<Vec<&str>>()
+                    .join(" ")
+            })
+            .collect();
+        rows.join("\n")
+    }
+
+    fn count_char(&self, c: char) -> usize {
+        self.chars().filter(|&x| x == c).count()
+    }
+
+    fn first_difference(&self, other: &str) -> Option<usize> {
+        let mut left = self.chars();
+        let mut right = other.chars();
+        let mut index = 0;
+        loop {
+            match (left.next(), right.next()) {
+                (None, None) => return None,
+                (Some(a), Some(b)) if a == b => index += 1,
+                _ => return Some(index),
+            }
+        }
+    }
+
+    fn strip_smart_quotes(&self) -> String {
+        let mut s = String::with_capacity(self.len());
+        for c in self.chars() {
+            match c {
+                '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => s.push('"'),
+                '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => s.push('\''),
+                '\u{2014}' => s.push_str("--"),
+                '\u{2013}' => s.push('-'),
+                '\u{2026}' => s.push_str("..."),
+                _ => s.push(c),
+            }
+        }
+        s
+    }
+
+    fn to_smart_quotes(&self) -> String {
+        let mut s = String::with_capacity(self.len());
+        let mut previous: Option<char> = None;
+        let mut chars = self.chars().peekable();
+        while let Some(c) = chars.next() {
+            let opening = match previous {
+                None => true,
+                Some(p) => {
+                    p.is_whitespace()
+                        || matches!(p, '(' | '[' | '{' | '\u{2014}' | '\u{201C}' | '\u{2018}')
+                }
+            };
+            let smart = match c {
+                '"' if opening => '\u{201C}',
+                '"' => '\u{201D}',
+                '\'' if opening => '\u{2018}',
+                '\'' => '\u{2019}',
+                '-' if chars.peek() == Some(&'-') => {
+                    chars.next();
+                    '\u{2014}'
+                }
+                _ => c,
+            };
+            s.push(smart);
+            previous = Some(smart);
+        }
+        s
+    }
+
+    fn word_at(&self, char_index: usize) -> Option<&str> {
+        let (byte_index, c) = self.char_indices().nth(char_index)?;
+        if c.is_whitespace() {
+            return None;
+        }
+        let start = self[..byte_index]
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(index, c)| index + c.len_utf8());
+        let end = self[byte_index..]
+            .char_indices()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(self.len(), |(index, _)| byte_index + index);
+        Some(&self[start..end])
+    }
+
+    fn replace_range(&self, start: usize, end: usize, replacement: &str) -> String {
+        let char_to_byte = |index: usize| {
+            self.char_indices()
+                .nth(index)
+                .map_or(self.len(), |(b, _)| b)
+        };
+        let start = char_to_byte(start);
+        let end = char_to_byte(end).max(start);
+        let mut s = String::with_capacity(self.len() + replacement.len());
+        s.push_str(&self[..start]);
+        s.push_str(replacement);
+        s.push_str(&self[end..]);
+        s
+    }
+
+    fn map_lines<F: FnMut(&str) -> String>(&self, mut f: F) -> String {
+        let mut s = String::with_capacity(self.len());
+        for (content, ending) in split_line_endings(self) {
+            s.push_str(&f(content));
+            s.push_str(ending);
+        }
+        s
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_map_lines<F: Fn(&str) -> String + Send + Sync>(&self, f: F) -> String {
+        split_line_endings(self)
+            .into_par_iter()
+            .map(|(content, ending)| f(content) + ending)
+            .collect()
+    }
+
+    fn eq_ignore_whitespace(&self, other: &str) -> bool {
+        self.chars()
+            .filter(|c| !c.is_whitespace())
+            .eq(other.chars().filter(|c| !c.is_whitespace()))
+    }
+
+    fn bucket(&self, num_buckets: usize) -> usize {
+        if num_buckets == 0 {
+            return 0;
+        }
+        (fnv1a(self.as_bytes()) % num_buckets as u64) as usize
+    }
+
+    fn title_case_except(&self, exceptions: &[&str]) -> String {
+        let exceptions: Vec<String> = exceptions.iter().map(|e| e.to_lowercase()).collect();
+        let mut s = String::with_capacity(self.len());
+        let mut first = true;
+        for piece in self.split_inclusive(char::is_whitespace) {
+            let word = piece.trim_end_matches(char::is_whitespace);
+            if word.is_empty() {
+                s.push_str(piece);
+                continue;
+            }
+            let lower = word.to_lowercase();
+            let bare = lower.trim_matches(|c: char| !c.is_alphanumeric());
+            if !first && exceptions.iter().any(|e| e == bare) {
+                s.push_str(&lower);
+            } else {
+                let mut capitalize = true;
+                for c in lower.chars() {
+                    if capitalize && c.is_alphabetic() {
+                        s.extend(c.to_uppercase());
+                        capitalize = false;
+                    } else {
+                        s.push(c);
+                    }
+                }
+            }
+            s.push_str(&piece[word.len()..]);
+            first &= bare.is_empty();
+        }
+        s
+    }
+
+    fn word_count_opts(&self, opts: WordCountOptions) -> usize {
+        let is_number = |word: &str| word.chars().all(|c| c.is_numeric() || c == '.' || c == ',');
+        self.split_whitespace()
+            .flat_map(|token| token.split(move |c: char| !opts.hyphenated_as_one && c == '-'))
+            .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+            .filter(|word| !word.is_empty())
+            .filter(|word| opts.count_numbers || !is_number(word))
+            .filter(|word| word.chars().count() >= opts.min_length)
+            .count()
+    }
+
+    fn strip_zero_width(&self) -> String {
+        self.chars()
+            .filter(|c| !ZERO_WIDTH_CHARS.contains(c))
+            .collect()
+    }
+
+    fn strip_zero_width_keep_emoji(&self) -> String {
+        let mut s = String::with_capacity(self.len());
+        let mut chars = self.chars().peekable();
+        let mut previous = None;
+        while let Some(c) = chars.next() {
+            let joins_emoji = c == '\u{200D}'
+                && previous.is_some_and(|p| is_pictographic(p) || p == '\u{FE0F}')
+                && chars.peek().is_some_and(|&n| is_pictographic(n));
+            if !ZERO_WIDTH_CHARS.contains(&c) || joins_emoji {
+                s.push(c);
+                previous = Some(c);
+            }
+        }
+        s
+    }
+
+    fn has_mojibake(&self) -> bool {
+        let bytes: Vec<Option<u8>> = self.chars().map(single_byte_encoding).collect();
+        bytes.iter().enumerate().any(|(i, &b)| {
+            let len = match b {
+                Some(0xC2..=0xDF) => 2,
+                Some(0xE0..=0xEF) => 3,
+                Some(0xF0..=0xF4) => 4,
+                _ => return false,
+            };
+            let sequence: Option<Vec<u8>> = bytes
+                .get(i..i + len)
+                .and_then(|s| s.iter().copied().collect());
+            sequence.is_some_and(|s| core::str::from_utf8(&s).is_ok())
+        })
+    }
+
+    fn fix_mojibake(&self) -> String {
+        self.chars()
+            .map(single_byte_encoding)
+            .collect::<Option<Vec<u8>>>()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .unwrap_or_else(|| self.to_string())
+    }
+
+    fn size_report(&self) -> SizeReport {
+        SizeReport {
+            bytes: self.len(),
+            chars: self.chars().count(),
+            graphemes: graphemes(self).count(),
+            display_width: UnicodeWidthStr::width(self.as_str()),
+            lines: self.lines().count(),
+        }
+    }
+
+    fn truncate_bytes_ellipsis(&self, max_bytes: usize) -> String {
+        if self.len() <= max_bytes {
+            return self.clone();
+        }
+        let ellipsis = '…';
+        let (budget, suffix) = match max_bytes.checked_sub(ellipsis.len_utf8()) {
+            Some(budget) => (budget, Some(ellipsis)),
+            None => (max_bytes, None),
+        };
+        let mut end = budget;
+        while !self.is_char_boundary(end) {
+            end -= 1;
+        }
+        let mut s = String::with_capacity(max_bytes);
+        s.push_str(&self[..end]);
+        s.extend(suffix);
+        s
+    }
+
+    fn pad(&self, left: usize, right: usize, c: char) -> String {
+        let mut s = String::with_capacity(self.len() + (left + right) * c.len_utf8());
+        s.extend(core::iter::repeat_n(c, left));
+        s.push_str(self);
+        s.extend(core::iter::repeat_n(c, right));
+        s
+    }
+
+    fn strip_numeric_suffix(&self) -> (String, Option<u64>) {
+        let base = self.trim_end_matches(|c: char| c.is_ascii_digit());
+        match self[base.len()..].parse() {
+            Ok(n) => (base.to_string(), Some(n)),
+            Err(_) => (self.clone(), None),
+        }
+    }
+
+    fn increment(&self) -> String {
+        let base = self.trim_end_matches(|c: char| c.is_ascii_digit());
+        if base.len() == self.len() {
+            return format!("{}2", self);
+        }
+        let mut digits: Vec<u8> = self[base.len()..].bytes().collect();
+        let mut carry = true;
+        for d in digits.iter_mut().rev() {
+            if *d == b'9' {
+                *d = b'0';
+            } else {
+                *d += 1;
+                carry = false;
+                break;
+            }
+        }
+        let mut s = String::with_capacity(self.len() + 1);
+        s.push_str(base);
+        if carry {
+            s.push('1');
+        }
+        s.extend(digits.into_iter().map(char::from));
+        s
+    }
+
+    fn split_on_any_keep(&self, delimiters: &[char]) -> Vec<String> {
+        let mut tokens = Vec::new();
+        let mut start = 0;
+        for (index, delimiter) in self.match_indices(delimiters) {
+            if start < index {
+                tokens.push(self[start..index].to_string());
+            }
+            tokens.push(delimiter.to_string());
+            start = index + delimiter.len();
+        }
+        if start < self.len() {
+            tokens.push(self[start..].to_string());
+        }
+        tokens
+    }
+
+    fn has_balanced_quotes(&self) -> bool {
+        let mut open = None;
+        let mut chars = self.chars();
+        while let Some(c) = chars.next() {
+            match open {
+                None if c == '\'' || c == '"' => open = Some(c),
+                None => {}
+                Some(_) if c == '\\' => {
+                    chars.next();
+                }
+                Some(quote) if c == quote => open = None,
+                Some(_) => {}
+            }
+        }
+        open.is_none()
+    }
+
+    fn word_wrap_hyphenate(&self, width: usize) -> String {
+        wrap_words(self, width, true).join("\n")
+    }
+
+    fn capitalize_sentences(&self) -> String {
+        let mut s = String::with_capacity(self.len());
+        let mut capitalize = true;
+        let mut after_terminator = false;
+        for c in self.chars() {
+            if capitalize && c.is_alphabetic() {
+                s.extend(c.to_uppercase());
+                capitalize = false;
+            } else {
+                if after_terminator && c.is_whitespace() {
+                    capitalize = true;
+                }
+                s.push(c);
+            }
+            after_terminator = c == '.' || c == '!' || c == '?';
+        }
+        s
+    }
+
+    fn squeeze_punctuation(&self) -> String {
+        self.squeeze_punctuation_to(1)
+    }
+
+    fn squeeze_punctuation_to(&self, max_run: usize) -> String {
+        let max_run = max_run.max(1);
+        let mut s = String::with_capacity(self.len());
+        let mut previous = None;
+        let mut run = 0;
+        for c in self.chars() {
+            if previous == Some(c) {
+                run += 1;
+            } else {
+                run = 1;
+            }
+            previous = Some(c);
+            if run <= max_run || CharClass::of(c) != CharClass::Punctuation {
+                s.push(c);
+            }
+        }
+        s
+    }
+
+    fn truncate_html(&self, max_text_len: usize) -> String {
+        let mut s = String::with_capacity(self.len());
+        let mut open: Vec<String> = Vec::new();
+        let mut remaining = max_text_len;
+        for token in html_tokens(self) {
+            match token {
+                HtmlToken::Text(text) => {
+                    let mut end = 0;
+                    while remaining > 0 {
+                        match next_html_char(&text[end..]) {
+                            Some((_, len)) => end += len,
+                            None => break,
+                        }
+                        remaining -= 1;
+                    }
+                    s.push_str(&text[..end]);
+                    if end < text.len() {
+                        break;
+                    }
+                }
+                HtmlToken::Tag {
+                    raw,
+                    name,
+                    closing,
+                    self_closing,
+                } => {
+                    if closing {
+                        if let Some(index) = open.iter().rposition(|n| *n == name) {
+                            for inner in open.drain(index + 1..).rev() {
+                                s.push_str("</");
+                                s.push_str(&inner);
+                                s.push('>');
+                            }
+                            open.truncate(index);
+                        }
+                    } else if remaining == 0 {
+                        break;
+                    } else if !self_closing
+                        && !name.is_empty()
+                        && !HTML_VOID_ELEMENTS.contains(&name.as_str())
+                    {
+                        open.push(name);
+                    }
+                    s.push_str(raw);
+                }
+            }
+        }
+        for name in open.iter().rev() {
+            s.push_str("</");
+            s.push_str(name);
+            s.push('>');
+        }
+        s
+    }
+
+    fn visible_text_len(&self) -> usize {
+        html_tokens(self)
+            .into_iter()
+            .map(|token| match token {
+                HtmlToken::Text(mut text) => {
+                    let mut count = 0;
+                    while let Some((_, len)) = next_html_char(text) {
+                        text = &text[len..];
+                        count += 1;
+                    }
+                    count
+                }
+                HtmlToken::Tag { .. } => 0,
+            })
+            .sum()
+    }
+
+    fn html_to_text(&self) -> String {
+        let mut s = String::with_capacity(self.len());
+        let mut pending_newlines = 0;
+        let mut pending_space = false;
+        let mut skipping: Option<String> = None;
+        for token in html_tokens(self) {
+            match token {
+                HtmlToken::Tag { name, closing, .. } => {
+                    if let Some(skipped) = &skipping {
+                        if closing && *skipped == name {
+                            skipping = None;
+                        }
+                        continue;
+                    }
+                    match name.as_str() {
+                        "script" | "style" if !closing => skipping = Some(name),
+                        "br" => pending_newlines += 1,
+                        "p" => pending_newlines = pending_newlines.max(2),
+                        "div" | "li" | "ul" | "ol" | "tr" | "blockquote" | "h1" | "h2" | "h3"
+                        | "h4" | "h5" | "h6" => pending_newlines = pending_newlines.max(1),
+                        _ => {}
+                    }
+                }
+                HtmlToken::Text(_) if skipping.is_some() => {}
+                HtmlToken::Text(mut text) => {
+                    while let Some((c, len)) = next_html_char(text) {
+                        text = &text[len..];
+                        if c.is_whitespace() {
+                            pending_space = true;
+                            continue;
+                        }
+                        if !s.is_empty() {
+                            if pending_newlines > 0 {
+                                s.extend(core::iter::repeat_n('\n', pending_newlines));
+                            } else if pending_space {
+                                s.push(' ');
+                            }
+                        }
+                        s.push(c);
+                        pending_newlines = 0;
+                        pending_space = false;
+                    }
+                }
+            }
+        }
+        s
+    }
+
+    fn levenshtein(&self, other: &str) -> usize {
+        let a: Vec<char> = self.chars().collect();
+        let b: Vec<char> = other.chars().collect();
+        let mut previous: Vec<usize> = (0..=b.len()).collect();
+        let mut current = vec![0; b.len() + 1];
+        for (i, &ca) in a.iter().enumerate() {
+            current[0] = i + 1;
+            for (j, &cb) in b.iter().enumerate() {
+                let substitution = previous[j] + usize::from(ca != cb);
+                current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+            }
+            core::mem::swap(&mut previous, &mut current);
+        }
+        previous[b.len()]
+    }
+
+    fn levenshtein_within(&self, other: &str, max: usize) -> Option<usize> {
+        let a: Vec<char> = self.chars().collect();
+        let b: Vec<char> = other.chars().collect();
+        if a.len().abs_diff(b.len()) > max {
+            return None;
+        }
+        let over = max + 1;
+        let mut previous: Vec<usize> = (0..=b.len()).map(|j| j.min(over)).collect();
+        let mut current = vec![over; b.len() + 1];
+        for (i, &ca) in a.iter().enumerate() {
+            let row = i + 1;
+            let from = row.saturating_sub(max).max(1);
+            let to = (row + max).min(b.len());
+            current[0] = row.min(over);
+            if from > 1 {
+                current[from - 1] = over;
+            }
+            let mut row_min = current[0];
+            for j in from..=to {
+                let substitution = previous[j - 1] + usize::from(ca != b[j - 1]);
+                let cell = substitution
+                    .min(previous[j] + 1)
+                    .min(current[j - 1] + 1)
+                    .min(over);
+                current[j] = cell;
+                row_min = row_min.min(cell);
+            }
+            if to < b.len() {
+                current[to + 1] = over;
+            }
+            if row_min > max {
+                return None;
+            }
+            core::mem::swap(&mut previous, &mut current);
+        }
+        Some(previous[b.len()]).filter(|&distance| distance <= max)
+    }
+
+    fn damerau_levenshtein(&self, other: &str) -> usize {
+        let a: Vec<char> = self.chars().collect();
+        let b: Vec<char> = other.chars().collect();
+        let mut before_previous = vec![0; b.len() + 1];
+        let mut previous: Vec<usize> = (0..=b.len()).collect();
+        let mut current = vec![0; b.len() + 1];
+        for i in 0..a.len() {
+            current[0] = i + 1;
+            for j in 0..b.len() {
+                let substitution = previous[j] + usize::from(a[i] != b[j]);
+                let mut cell = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+                if i > 0 && j > 0 && a[i] == b[j - 1] && a[i - 1] == b[j] {
+                    cell = cell.min(before_previous[j - 1] + 1);
+                }
+                current[j + 1] = cell;
+            }
+            core::mem::swap(&mut before_previous, &mut previous);
+            core::mem::swap(&mut previous, &mut current);
+        }
+        previous[b.len()]
+    }
+
+    fn align_decimal(&self, int_width: usize, frac_width: usize) -> String {
+        let (int_part, frac_part) = match self.split_once('.') {
+            Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+            None => (self.as_str(), None),
+        };
+        let digits = int_part.strip_prefix(['-', '+']).unwrap_or(int_part);
+        let is_number = digits.chars().all(|c| c.is_ascii_digit())
+            && frac_part.is_none_or(|f| f.chars().all(|c| c.is_ascii_digit()))
+            && digits.len() + frac_part.map_or(0, str::len) > 0;
+        if !is_number {
+            return self.clone();
+        }
+        let mut s = String::with_capacity(int_width + frac_width + 1);
+        s.extend(core::iter::repeat_n(
+            ' ',
+            int_width.saturating_sub(int_part.len()),
+        ));
+        s.push_str(int_part);
+        let frac_part = frac_part.unwrap_or("");
+        if !frac_part.is_empty() || frac_width > 0 {
+            s.push('.');
+            s.push_str(frac_part);
+            s.extend(core::iter::repeat_n(
+                '0',
+                frac_width.saturating_sub(frac_part.len()),
+            ));
+        }
+        s
+    }
+
+    fn dedent_min(&self) -> String {
+        let lines: Vec<&str> = self.split_inclusive('\n').collect();
+        let common = lines
+            .iter()
+            .map(|line| line_content(line))
+            .filter(|content| !content.trim().is_empty())
+            .map(|content| &content[..content.len() - content.trim_start().len()])
+            .reduce(|common, indent| &common[..common_prefix_bytes(common, indent)])
+            .unwrap_or("");
+        let mut s = String::with_capacity(self.len());
+        for line in lines {
+            let content = line_content(line);
+            let strip = if content.trim().is_empty() {
+                common_prefix_bytes(common, content)
+            } else {
+                common.len()
+            };
+            s.push_str(&line[strip..]);
+        }
+        s
+    }
+}
+
+/// Splits 'text' into extended grapheme clusters.
+#[cfg(feature = "unicode")]
+fn graphemes(text: &str) -> impl DoubleEndedIterator<Item = &str> + '_ {
+    UnicodeSegmentation::graphemes(text, true)
+}
+
+/// Splits 'text' into single chars, standing in for grapheme clusters when the `unicode` feature is disabled.
+#[cfg(not(feature = "unicode"))]
+fn graphemes(text: &str) -> impl DoubleEndedIterator<Item = &str> + '_ {
+    text.char_indices()
+        .map(move |(index, c)| &text[index..index + c.len_utf8()])
+}
+
+/// Groups the lines of a text into paragraphs, which are separated by one or more blank lines.
+fn paragraph_lines(text: &str) -> Vec<Vec<&str>> {
+    let mut paragraphs = Vec::new();
+    let mut current = Vec::new();
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(current);
+                current = Vec::new();
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push(current);
+    }
+    paragraphs
+}
+
+/// Greedily wraps whitespace separated words into lines of at most 'width' columns of display width.
+/// A word longer than 'width' is put on a line of its own, or, with 'hyphenate' and a width of at least 2,
+/// broken across lines with a hyphen at the end of each full part.
+fn wrap_words(text: &str, width: usize, hyphenate: bool) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_width = 0;
+    for mut word in text.split_whitespace() {
+        let mut word_width = UnicodeWidthStr::width(word);
+        if hyphenate && width > 1 && word_width > width {
+            if line_width > 0 {
+                lines.push(core::mem::take(&mut line));
+                line_width = 0;
+            }
+            while word_width > width {
+                let mut end = 0;
+                let mut part_width = 0;
+                for (index, c) in word.char_indices() {
+                    let char_width = UnicodeWidthChar::width(c).unwrap_or(0);
+                    if index > 0 && part_width + char_width > width - 1 {
+                        break;
+                    }
+                    end = index + c.len_utf8();
+                    part_width += char_width;
+                }
+                lines.push(format!("{}-", &word[..end]));
+                word = &word[end..];
+                word_width -= part_width;
+            }
+        }
+        if line_width > 0 && line_width + 1 + word_width > width {
+            lines.push(core::mem::take(&mut line));
+            line_width = 0;
+        }
+        if line_width > 0 {
+            line.push(' ');
+            line_width += 1;
+        }
+        line.push_str(word);
+        line_width += word_width;
+    }
+    if line_width > 0 || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Character counts per class, as returned by `ExtString::count_by_class`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CharClassCounts {
+    /// Number of alphabetic characters.
+    pub letters: usize,
+    /// Number of numeric characters.
+    pub digits: usize,
+    /// Number of whitespace characters.
+    pub whitespace: usize,
+    /// Number of punctuation characters.
+    pub punctuation: usize,
+    /// Number of characters that fall in none of the other classes.
+    pub other: usize,
+}
+
+/// The class of a character, as used by `ExtString::count_by_class` and `ExtString::class_runs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CharClass {
+    /// An alphabetic character.
+    Letter,
+    /// A numeric character.
+    Digit,
+    /// A whitespace character.
+    Whitespace,
+    /// ASCII punctuation and symbols, as well as general and CJK punctuation.
+    Punctuation,
+    /// Anything else.
+    Other,
+}
+
+impl CharClass {
+    /// Classifies a single character.
+    pub fn of(c: char) -> CharClass {
+        if c.is_alphabetic() {
+            CharClass::Letter
+        } else if c.is_numeric() {
+            CharClass::Digit
+        } else if c.is_whitespace() {
+            CharClass::Whitespace
+        } else if c.is_ascii_punctuation() || is_general_punctuation(c) {
+            CharClass::Punctuation
+        } else {
+            CharClass::Other
+        }
+    }
+}
+
+/// Checks for punctuation outside of ASCII: the General Punctuation block and the CJK punctuation blocks.
+fn is_general_punctuation(c: char) -> bool {
+    matches!(
+        c,
+        '\u{2010}'..='\u{2027}'
+            | '\u{2030}'..='\u{205E}'
+            | '\u{3001}'..='\u{3003}'
+            | '\u{3008}'..='\u{3011}'
+            | '¡'
+            | '¿'
+            | '«'
+            | '»'
+    )
+}
+
+/// Checks for the invisible characters of the Unicode categories Cf (format), Zl (line separator)
+/// and Zp (paragraph separator), such as zero-width spaces, bidi controls and byte order marks.
+fn is_format_or_separator(c: char) -> bool {
+    matches!(
+        c,
+        '\u{AD}'
+            | '\u{600}'..='\u{605}'
+            | '\u{61C}'
+            | '\u{6DD}'
+            | '\u{70F}'
+            | '\u{890}'..='\u{891}'
+            | '\u{8E2}'
+            | '\u{180E}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{2028}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{206F}'
+            | '\u{FEFF}'
+            | '\u{FFF9}'..='\u{FFFB}'
+            | '\u{110BD}'
+            | '\u{110CD}'
+            | '\u{13430}'..='\u{1343F}'
+            | '\u{1BCA0}'..='\u{1BCA3}'
+            | '\u{1D173}'..='\u{1D17A}'
+            | '\u{E0001}'
+            | '\u{E0020}'..='\u{E007F}'
+    )
+}
+
+/// Returns the byte length of the prefix of 'haystack' that matches the lowercased 'needle', ignoring case.
+/// The final sigma "ς" is treated as "σ", since lowercasing a single "Σ" cannot tell them apart.
+fn prefix_len_ci(haystack: &str, needle: &[char]) -> Option<usize> {
+    let fold_sigma = |c: char| if c == 'ς' { 'σ' } else { c };
+    let mut matched = 0;
+    for (index, c) in haystack.char_indices() {
+        for lower in c.to_lowercase() {
+            if needle.get(matched).map(|&n| fold_sigma(n)) != Some(fold_sigma(lower)) {
+                return None;
+            }
+            matched += 1;
+        }
+        if matched == needle.len() {
+            return Some(index + c.len_utf8());
+        }
+    }
+    None
+}
+
+#[cfg(feature = "std")]
+const ENGLISH_ALPHABET: [char; 26] = [
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
+    't', 'u', 'v', 'w', 'x', 'y', 'z',
+];
+
+/// Maps a lowercase accented Latin letter to its unaccented form. Combining diacritical marks map to nothing.
+fn fold_accent(c: char) -> Option<&'static str> {
+    let base = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ð' | 'ď' | 'đ' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' | 'ĸ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' | 'ŉ' | 'ŋ' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ſ' => "s",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        'æ' => "ae",
+        'œ' => "oe",
+        'ß' => "ss",
+        'þ' => "th",
+        'ĳ' => "ij",
+        '\u{300}'..='\u{36F}' => "",
+        _ => return None,
+    };
+    Some(base)
+}
+
+/// Checks if a grapheme cluster is an emoji: it either starts with a pictographic character
+/// or is explicitly requested in emoji presentation (U+FE0F) or as a keycap (U+20E3).
+fn is_emoji_grapheme(g: &str) -> bool {
+    let first = match g.chars().next() {
+        Some(c) => c,
+        None => return false,
+    };
+    is_pictographic(first) || g.chars().any(|c| c == '\u{FE0F}' || c == '\u{20E3}')
+}
+
+/// Checks if a character lies in one of the blocks holding pictographic emoji, or is one of the few
+/// Extended_Pictographic characters of the Miscellaneous Technical and Miscellaneous Symbols and Arrows blocks.
+/// Ordinary symbols of those two blocks, such as "⌘" or "⬌", are not emoji.
+fn is_pictographic(c: char) -> bool {
+    matches!(
+        c,
+        '\u{1F000}'..='\u{1FAFF}'
+            | '\u{2600}'..='\u{27BF}'
+            | '\u{231A}'..='\u{231B}'
+            | '\u{2328}'
+            | '\u{2388}'
+            | '\u{23CF}'
+            | '\u{23E9}'..='\u{23F3}'
+            | '\u{23F8}'..='\u{23FA}'
+            | '\u{2B05}'..='\u{2B07}'
+            | '\u{2B1B}'..='\u{2B1C}'
+            | '\u{2B50}'
+            | '\u{2B55}'
+    )
+}
+
+/// Zero-width characters removed by `ExtString::strip_zero_width`.
+const ZERO_WIDTH_CHARS: [char; 5] = ['\u{200B}', '\u{200C}', '\u{200D}', '\u{FEFF}', '\u{2060}'];
+
+/// Distance between printable ASCII and the Halfwidth and Fullwidth Forms block.
+const FULLWIDTH_OFFSET: u32 = 0xFEE0;
+
+/// Distance between the Hiragana and the Katakana blocks.
+const KANA_OFFSET: u32 = 0x60;
+
+/// Treats each line as a row of tab separated cells and returns the transposed table, so rows become columns.
+/// Ragged rows are padded with empty cells.
+pub fn transpose_lines(text: &str) -> String {
+    let rows: Vec<Vec<&str>> = text
+        .lines()
+        .map(|line| line.split('\t').collect())
+        .collect();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut lines = Vec::with_capacity(width);
+    for column in 0..width {
+        let cells: Vec<&str> = rows
+            .iter()
+            .map(|row| row.get(column).cloned().unwrap_or(""))
+            .collect();
+        lines.push(cells.join("\t"));
+    }
+    lines.join("\n")
+}
+
+/// The error returned by `ExtString::unescape`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnescapeError {
+    /// An unknown or malformed escape sequence starts at this byte offset.
+    InvalidEscape(usize),
+    /// The `\u{...}` escape at this byte offset is not a valid Unicode scalar value.
+    InvalidCodePoint(usize),
+    /// The string ends with a lone backslash.
+    TrailingBackslash,
+}
+
+impl fmt::Display for UnescapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UnescapeError::InvalidEscape(index) => {
+                write!(f, "invalid escape sequence at byte {}", index)
+            }
+            UnescapeError::InvalidCodePoint(index) => {
+                write!(f, "invalid code point at byte {}", index)
+            }
+            UnescapeError::TrailingBackslash => write!(f, "trailing backslash"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for UnescapeError {}
+
+/// The indentation style of a text, as returned by `ExtString::detect_indent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentStyle {
+    /// Lines are indented with tabs.
+    Tabs,
+    /// Lines are indented with spaces, this many per level.
+    Spaces(usize),
+    /// Lines mix tabs and spaces, or there is no indentation to go by.
+    Mixed,
+}
+
+/// Strips the "\n" or "\r\n" line ending from a line.
+fn line_content(line: &str) -> &str {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+/// Splits a text into its lines, each paired with its own line ending: "\n", "\r\n" or "" for a last unterminated line.
+fn split_line_endings(text: &str) -> Vec<(&str, &str)> {
+    text.split_inclusive('\n')
+        .map(|line| {
+            let content = line_content(line);
+            (content, &line[content.len()..])
+        })
+        .collect()
+}
+
+/// Joins reordered lines with their own line endings. If the unterminated line no longer comes last,
+/// it trades endings with the line that does, so no two lines run together and a missing trailing newline stays missing.
+fn join_line_endings(mut lines: Vec<(&str, &str)>) -> String {
+    if let Some(index) = lines.iter().position(|(_, ending)| ending.is_empty()) {
+        let last = lines.len() - 1;
+        let ending = lines[last].1;
+        lines[last].1 = "";
+        lines[index].1 = ending;
+    }
+    let mut s = String::new();
+    for (content, ending) in lines {
+        s.push_str(content);
+        s.push_str(ending);
+    }
+    s
+}
+
+/// Returns the length in bytes of the longest common prefix of 'a' and 'b', compared char by char
+/// so the result always lies on a char boundary of both.
+fn common_prefix_bytes(a: &str, b: &str) -> usize {
+    a.chars()
+        .zip(b.chars())
+        .take_while(|(x, y)| x == y)
+        .map(|(x, _)| x.len_utf8())
+        .sum()
+}
+
+/// Advances a SplitMix64 generator and returns its next value.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Sums ASCII digits for the Luhn checksum, doubling every second digit from the right.
+/// The rightmost digit is doubled when 'double_rightmost' is set.
+fn luhn_sum(digits: &str, double_rightmost: bool) -> u32 {
+    digits
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(index, b)| {
+            let digit = u32::from(b - b'0');
+            if (index % 2 == 0) == double_rightmost {
+                let doubled = digit * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                digit
+            }
+        })
+        .sum()
+}
+
+/// The RFC 4648 Base32 alphabet.
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// The error returned when decoding a binary-to-text encoding such as `ExtString::from_base32` fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The input contains a character outside the encoding's alphabet.
+    InvalidCharacter(char),
+    /// The input has a length no valid encoding can have.
+    InvalidLength,
+    /// The padding does not match the length of the data, or the unused bits at the end are not zero.
+    InvalidPadding,
+    /// The decoded bytes are not valid UTF-8.
+    InvalidUtf8,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::InvalidCharacter(c) => write!(f, "invalid character {:?}", c),
+            DecodeError::InvalidLength => write!(f, "invalid length"),
+            DecodeError::InvalidPadding => write!(f, "invalid padding"),
+            DecodeError::InvalidUtf8 => write!(f, "decoded bytes are not valid UTF-8"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for DecodeError {}
+
+/// The Bitcoin Base58 alphabet, which leaves out 0, O, I and l.
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// The number of cased letters below which `ExtString::is_shouting` never flags a string.
+const SHOUTING_MIN_LETTERS: usize = 4;
+
+/// Turns a text into a URL slug: lowercased, accents stripped,
+/// and every run of non-alphanumeric characters replaced with a single hyphen.
+#[cfg(feature = "std")]
+fn slugify(text: &str) -> String {
+    let folded = String::from(text).fold_for_search();
+    let words: Vec<&str> = folded
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    words.join("-")
+}
+
+/// Slugifies 'base' and, if the slug is already taken in 'existing', appends "-2", "-3" and so on until it is unique.
+#[cfg(feature = "std")]
+pub fn unique_slug(base: &str, existing: &HashSet<String>) -> String {
+    let slug = slugify(base);
+    if !existing.contains(&slug) {
+        return slug;
+    }
+    (2..)
+        .map(|n| format!("{}-{}", slug, n))
+        .find(|candidate| !existing.contains(candidate))
+        .unwrap()
+}
+
+/// Score for every matched character in `ExtString::fuzzy_score`.
+const FUZZY_MATCH: i32 = 1;
+/// Bonus for a match right after the previous one in `ExtString::fuzzy_score`.
+const FUZZY_CONSECUTIVE: i32 = 5;
+/// Bonus for a match at the start of a word in `ExtString::fuzzy_score`.
+const FUZZY_WORD_START: i32 = 8;
+
+/// Returns the rows of the built-in `ExtString::banner` font for a character.
+fn banner_glyph(c: char) -> [&'static str; 5] {
+    match c.to_ascii_uppercase() {
+        'A' => [" ### ", "#   #", "#####", "#   #", "#   #"],
+        'B' => ["#### ", "#   #", "#### ", "#   #", "#### "],
+        'C' => [" ####", "#    ", "#    ", "#    ", " ####"],
+        'D' => ["#### ", "#   #", "#   #", "#   #", "#### "],
+        'E' => ["#####", "#    ", "#### ", "#    ", "#####"],
+        'F' => ["#####", "#    ", "#### ", "#    ", "#    "],
+        'G' => [" ####", "#    ", "#  ##", "#   #", " ####"],
+        'H' => ["#   #", "#   #", "#####", "#   #", "#   #"],
+        'I' => ["#####", "  #  ", "  #  ", "  #  ", "#####"],
+        'J' => ["#####", "   # ", "   # ", "#  # ", " ##  "],
+        'K' => ["#   #", "#  # ", "###  ", "#  # ", "#   #"],
+        'L' => ["#    ", "#    ", "#    ", "#    ", "#####"],
+        'M' => ["#   #", "## ##", "# # #", "#   #", "#   #"],
+        'N' => ["#   #", "##  #", "# # #", "#  ##", "#   #"],
+        'O' => [" ### ", "#   #", "#   #", "#   #", " ### "],
+        'P' => ["#### ", "#   #", "#### ", "#    ", "#    "],
+        'Q' => [" ### ", "#   #", "# # #", "#  # ", " ## #"],
+        'R' => ["#### ", "#   #", "#### ", "#  # ", "#   #"],
+        'S' => [" ####", "#    ", " ### ", "    #", "#### "],
+        'T' => ["#####", "  #  ", "  #  ", "  #  ", "  #  "],
+        'U' => ["#   #", "#   #", "#   #", "#   #", " ### "],
+        'V' => ["#   #", "#   #", "#   #", " # # ", "  #  "],
+        'W' => ["#   #", "#   #", "# # #", "## ##", "#   #"],
+        'X' => ["#   #", " # # ", "  #  ", " # # ", "#   #"],
+        'Y' => ["#   #", " # # ", "  #  ", "  #  ", "  #  "],
+        'Z' => ["#####", "   # ", "  #  ", " #   ", "#####"],
+        '0' => [" ### ", "#  ##", "# # #", "##  #", " ### "],
+        '1' => ["  #  ", " ##  ", "  #  ", "  #  ", " ### "],
+        '2' => [" ### ", "#   #", "  ## ", " #   ", "#####"],
+        '3' => ["#### ", "    #", " ### ", "    #", "#### "],
+        '4' => ["#   #", "#   #", "#####", "    #", "    #"],
+        '5' => ["#####", "#    ", "#### ", "    #", "#### "],
+        '6' => [" ### ", "#    ", "#### ", "#   #", " ### "],
+        '7' => ["#####", "    #", "   # ", "  #  ", "  #  "],
+        '8' => [" ### ", "#   #", " ### ", "#   #", " ### "],
+        '9' => [" ### ", "#   #", " ####", "    #", " ### "],
+        _ => ["     "; 5],
+    }
+}
+
+/// Returns the length, in chars, of the longest prefix shared by all of the strings.
+/// An empty slice gives 0 and a single string gives its own length.
+pub fn common_prefix_len(strings: &[&str]) -> usize {
+    let (first, rest) = match strings.split_first() {
+        Some(split) => split,
+        None => return 0,
+    };
+    rest.iter().fold(first.chars().count(), |len, s| {
+        first
+            .chars()
+            .zip(s.chars())
+            .take(len)
+            .take_while(|(a, b)| a == b)
+            .count()
+    })
+}
+
+/// A string together with its grapheme count, char count and display width, measured once up front.
+/// Useful when the same string is measured or padded over and over, e.g. in tight formatting loops.
+/// It can either borrow or own the underlying string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MeasuredString<'a> {
+    text: Cow<'a, str>,
+    grapheme_count: usize,
+    char_count: usize,
+    display_width: usize,
+}
+
+impl<'a> MeasuredString<'a> {
+    /// Measures a borrowed or owned string.
+    pub fn new<S: Into<Cow<'a, str>>>(text: S) -> MeasuredString<'a> {
+        let text = text.into();
+        MeasuredString {
+            grapheme_count: graphemes(text.as_ref()).count(),
+            char_count: text.chars().count(),
+            display_width: UnicodeWidthStr::width(text.as_ref()),
+            text,
+        }
+    }
+
+    /// The underlying string.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The number of extended grapheme clusters.
+    pub fn grapheme_count(&self) -> usize {
+        self.grapheme_count
+    }
+
+    /// The number of characters.
+    pub fn char_count(&self) -> usize {
+        self.char_count
+    }
+
+    /// The width in terminal columns, where wide characters such as CJK count as 2.
+    pub fn display_width(&self) -> usize {
+        self.display_width
+    }
+
+    /// Same as `ExtString::pad_left`, using the cached char count.
+    pub fn pad_left(&self, pad_len: usize, c: char) -> String {
+        if pad_len <= self.char_count {
+            return self.text.to_string();
+        }
+        let repeat = pad_len - self.char_count;
+        let mut pad = String::with_capacity(self.text.len() + repeat * c.len_utf8());
+        for _ in 0..repeat {
+            pad.push(c);
+        }
+        pad.push_str(&self.text);
+        pad
+    }
+
+    /// Same as `ExtString::pad_right`, using the cached char count.
+    pub fn pad_right(&self, pad_len: usize, c: char) -> String {
+        if pad_len <= self.char_count {
+            return self.text.to_string();
+        }
+        let repeat = pad_len - self.char_count;
+        let mut pad = String::with_capacity(self.text.len() + repeat * c.len_utf8());
+        pad.push_str(&self.text);
+        for _ in 0..repeat {
+            pad.push(c);
+        }
+        pad
+    }
+}
+
+/// Where a `Padder` places the text within the padded width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alignment {
+    /// Text on the left, padded on the right, like `ExtString::pad_right`.
+    Left,
+    /// Text on the right, padded on the left, like `ExtString::pad_left`.
+    Right,
+    /// Text in the middle. When the padding can't be split evenly, the extra character goes on the right.
+    Center,
+}
+
+/// Pads many strings to the same width with the same fill character and alignment.
+/// `pad_into` appends to a caller-provided buffer, so table rows can be rendered without an allocation per cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Padder {
+    width: usize,
+    fill: char,
+    alignment: Alignment,
+}
+
+impl Padder {
+    /// Creates a padder for the given width in chars, fill character and alignment.
+    pub fn new(width: usize, fill: char, alignment: Alignment) -> Padder {
+        Padder {
+            width,
+            fill,
+            alignment,
+        }
+    }
+
+    /// Pads 's' into a new string.
+    /// If the width is shorter or equal to the character length, a simple copy of 's' is returned.
+    pub fn pad(&self, s: &str) -> String {
+        let mut buf = String::with_capacity(s.len() + self.width * self.fill.len_utf8());
+        self.pad_into(s, &mut buf);
+        buf
+    }
+
+    /// Appends 's', padded, to the end of 'buf'.
+    pub fn pad_into(&self, s: &str, buf: &mut String) {
+        let repeat = self.width.saturating_sub(s.chars().count());
+        let (left, right) = match self.alignment {
+            Alignment::Left => (0, repeat),
+            Alignment::Right => (repeat, 0),
+            Alignment::Center => (repeat / 2, repeat - repeat / 2),
+        };
+        buf.reserve(s.len() + repeat * self.fill.len_utf8());
+        buf.extend(core::iter::repeat_n(self.fill, left));
+        buf.push_str(s);
+        buf.extend(core::iter::repeat_n(self.fill, right));
+    }
+}
+
+/// Applies 'f' to every string in 'items' and collects the results, sizing the output up front.
+/// Meant for running an `ExtString` method over a whole collection, e.g. `transform_all(names, String::sentence_case)`.
+pub fn transform_all<I, F>(items: I, mut f: F) -> Vec<String>
+where
+    I: IntoIterator<Item = String>,
+    F: FnMut(&String) -> String,
+{
+    let items = items.into_iter();
+    let mut transformed = Vec::with_capacity(items.size_hint().0);
+    for item in items {
+        transformed.push(f(&item));
+    }
+    transformed
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Computes the 64-bit FNV-1a hash of 'bytes'.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Options for `ExtString::word_count_opts`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordCountOptions {
+    /// Whether numbers such as "2024" or "3.14" count as words. Defaults to true.
+    pub count_numbers: bool,
+    /// Whether "well-known" counts as one word rather than two. Defaults to true.
+    pub hyphenated_as_one: bool,
+    /// The minimum length, in chars, of a word to be counted. Defaults to 1.
+    pub min_length: usize,
+}
+
+impl Default for WordCountOptions {
+    fn default() -> WordCountOptions {
+        WordCountOptions {
+            count_numbers: true,
+            hyphenated_as_one: true,
+            min_length: 1,
+        }
+    }
+}
+
+/// Windows-1252 characters for the bytes 0x80 to 0x9F, where it differs from Latin-1.
+/// Unassigned bytes keep their Latin-1 control character.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8D}', '\u{17D}', '\u{8F}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}', '\u{17E}', '\u{178}',
+];
+
+/// Returns the byte a char is encoded as in Latin-1 or Windows-1252, if any.
+fn single_byte_encoding(c: char) -> Option<u8> {
+    if (c as u32) < 0x100 {
+        return Some(c as u8);
+    }
+    WINDOWS_1252_HIGH
+        .iter()
+        .position(|&w| w == c)
+        .map(|i| 0x80 + i as u8)
+}
+
+/// The different sizes of a string, as returned by `ExtString::size_report`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SizeReport {
+    /// Length in UTF-8 bytes.
+    pub bytes: usize,
+    /// Number of chars, i.e. Unicode scalar values.
+    pub chars: usize,
+    /// Number of grapheme clusters.
+    pub graphemes: usize,
+    /// Width in terminal columns, where wide characters such as CJK take two columns.
+    pub display_width: usize,
+    /// Number of lines, with a trailing newline not starting a new line.
+    pub lines: usize,
+}
+
+/// A piece of an HTML fragment, as produced by `html_tokens`.
+enum HtmlToken<'a> {
+    /// Text between tags, with its entities still encoded.
+    Text(&'a str),
+    /// A tag such as `<b>`, `</b>` or `<br/>`. The name is lowercased, and empty for comments and doctypes.
+    Tag {
+        raw: &'a str,
+        name: String,
+        closing: bool,
+        self_closing: bool,
+    },
+}
+
+/// Elements that never have a closing tag.
+const HTML_VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Splits an HTML fragment into text and tags. A '<' that does not start a tag is kept as text.
+/// This is not a full parser: a '>' inside an attribute value or a comment ends the tag early.
+fn html_tokens(html: &str) -> Vec<HtmlToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut text_start = 0;
+    let mut pos = 0;
+    while let Some(offset) = html[pos..].find('<') {
+        let start = pos + offset;
+        pos = start + 1;
+        let starts_tag =
+            html[pos..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '!');
+        let end = match html[start..].find('>') {
+            Some(len) if starts_tag => start + len + 1,
+            _ => continue,
+        };
+        if text_start < start {
+            tokens.push(HtmlToken::Text(&html[text_start..start]));
+        }
+        let raw = &html[start..end];
+        let inner = &raw[1..raw.len() - 1];
+        let name = if inner.starts_with('!') {
+            String::new()
+        } else {
+            inner
+                .trim_start_matches('/')
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or("")
+                .to_ascii_lowercase()
+        };
+        tokens.push(HtmlToken::Tag {
+            raw,
+            name,
+            closing: inner.starts_with('/'),
+            self_closing: inner.ends_with('/'),
+        });
+        text_start = end;
+        pos = end;
+    }
+    if text_start < html.len() {
+        tokens.push(HtmlToken::Text(&html[text_start..]));
+    }
+    tokens
+}
+
+/// Decodes the first character of some HTML text, returning it with its encoded length in bytes.
+/// Numeric entities and "&amp;", "&lt;", "&gt;", "&quot;", "&apos;" and "&nbsp;" are decoded,
+/// anything else, including unknown entities, is taken literally.
+fn next_html_char(text: &str) -> Option<(char, usize)> {
+    let c = text.chars().next()?;
+    if c == '&' {
+        let end = text
+            .char_indices()
+            .take(12)
+            .find(|&(_, c)| c == ';')
+            .map(|(i, _)| i);
+        if let Some(end) = end {
+            let entity = &text[1..end];
+            let decoded = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{A0}'),
+                _ => entity.strip_prefix('#').and_then(|number| {
+                    match number.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => number.parse().ok(),
+                    }
+                    .and_then(core::char::from_u32)
+                }),
+            };
+            if let Some(decoded) = decoded {
+                return Some((decoded, end + 1));
+            }
+        }
+    }
+    Some((c, c.len_utf8()))
+}
+
+/// Renders a progress bar such as "[#####-----]", with 'width' cells between the brackets filled to 'fraction'.
+/// The fraction is clamped to 0.0..=1.0 and rounded to the nearest cell.
+pub fn progress_bar(fraction: f64, width: usize) -> String {
+    let filled = (fraction.clamp(0.0, 1.0) * width as f64 + 0.5) as usize;
+    let mut s = String::with_capacity(width + 2);
+    s.push('[');
+    s.extend(core::iter::repeat_n('#', filled));
+    s.extend(core::iter::repeat_n('-', width - filled));
+    s.push(']');
+    s
+}
+
+/// Partially filled blocks, from one to seven eighths.
+const PARTIAL_BLOCKS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// Same as `progress_bar`, but drawn with Unicode blocks that fill each cell in eighths for a smoother bar,
+/// such as "[███▌      ]". Empty cells are spaces.
+pub fn progress_bar_smooth(fraction: f64, width: usize) -> String {
+    let eighths = (fraction.clamp(0.0, 1.0) * (width * 8) as f64 + 0.5) as usize;
+    let (full, partial) = (eighths / 8, eighths % 8);
+    let mut s = String::with_capacity(width * '█'.len_utf8() + 2);
+    s.push('[');
+    s.extend(core::iter::repeat_n('█', full));
+    if partial > 0 {
+        s.push(PARTIAL_BLOCKS[partial - 1]);
+    }
+    s.extend(core::iter::repeat_n(
+        ' ',
+        width - full - usize::from(partial > 0),
+    ));
+    s.push(']');
+    s
+}
+
+/// Block characters used by `sparkline`, from lowest to highest.
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Renders the values as a sparkline such as "▁▃▅█", scaling them between the smallest and the largest value.
+/// If all values are equal, a flat line of mid-level blocks is returned. An empty slice gives an empty string.
+pub fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let top = (SPARK_LEVELS.len() - 1) as f64;
+    values
+        .iter()
+        .map(|&value| {
+            if max > min {
+                SPARK_LEVELS[((value - min) / (max - min) * top + 0.5) as usize]
+            } else {
+                SPARK_LEVELS[SPARK_LEVELS.len() / 2 - 1]
+            }
+        })
+        .collect()
+}
+
+/// Renders a GitHub-flavored Markdown table with a header row, a separator row and one row per entry of 'rows'.
+/// Cells are padded to a common display width per column. Short rows are padded with empty cells,
+/// and pipes inside cells are escaped as "\|".
+pub fn markdown_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).fold(headers.len(), usize::max);
+    let escape = |cell: &str| cell.replace('|', "\\|");
+    let header: Vec<String> = headers.iter().map(|h| escape(h)).collect();
+    let body: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| row.iter().map(|cell| escape(cell)).collect())
+        .collect();
+    let mut widths = vec![3; columns];
+    for row in core::iter::once(&header).chain(body.iter()) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(UnicodeWidthStr::width(cell.as_str()));
+        }
+    }
+    let render = |s: &mut String, row: &[String]| {
+        s.push('|');
+        for (i, width) in widths.iter().enumerate() {
+            let cell = row.get(i).map_or("", String::as_str);
+            s.push(' ');
+            s.push_str(cell);
+            s.extend(core::iter::repeat_n(
+                ' ',
+                width - UnicodeWidthStr::width(cell),
+            ));
+            s.push_str(" |");
+        }
+        s.push('\n');
+    };
+    let mut s = String::new();
+    render(&mut s, &header);
+    s.push('|');
+    for &width in &widths {
+        s.push(' ');
+        s.extend(core::iter::repeat_n('-', width));
+        s.push_str(" |");
+    }
+    s.push('\n');
+    for row in &body {
+        render(&mut s, row);
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use crate::unique_slug;
+    use crate::{
+        common_prefix_len, markdown_table, progress_bar, progress_bar_smooth, sparkline,
+        transform_all, transpose_lines, Alignment, CharClass, CharClassCounts, DecodeError,
+        ExtString, IndentStyle, MeasuredString, Padder, SizeReport, UnescapeError,
+        WordCountOptions,
+    };
+    use std::cmp::Ordering;
+    #[cfg(feature = "std")]
+    use std::collections::HashSet;
+    #[cfg(feature = "unicode")]
+    use unicode_segmentation::UnicodeSegmentation;
+    use unicode_width::UnicodeWidthStr;
+
+    #[test]
+    fn test_reverse() {
+        let original = String::from("123456789");
+        assert_eq!(original.reverse(), "987654321");
+        let chinese = String::from("汉字漢字");
+        assert_eq!(chinese.reverse(), "字漢字汉");
+        let mangled = String::from("גבאabc1汉字漢字");
+        assert_eq!(mangled.reverse(), "字漢字汉1cbaאבג");
+        #[cfg(feature = "unicode")]
+        {
+            let weird = String::from("नमस्ते्");
+            assert_eq!(weird.reverse(), "ते्स्मन");
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "unicode"))]
+    fn test_reverse_without_unicode() {
+        assert_eq!("\u{301}eab", String::from("bae\u{301}").reverse());
+        assert_eq!("字汉cba", String::from("abc汉字").reverse());
+        assert_eq!(vec!["👋", "🏽"], String::from("Hi 👋🏽!").emoji_list());
+    }
+
+    #[test]
+    fn test_pad_left() {
+        let s = "12345";
+        let space = ' ';
+        assert_eq!("12345", String::from(s).pad_left(3, space));
+        assert_eq!("     12345", String::from(s).pad_left(10, space));
+    }
+
+    #[test]
+    fn test_pad_right() {
+        let s = "12345";
+        let space = ' ';
+        assert_eq!("12345", String::from(s).pad_right(3, space));
+        assert_eq!("12345     ", String::from(s).pad_right(10, space));
+    }
+
+    #[test]
+    fn test_pad_left_str() {
+        let s = "12345";
+        let padding = "qwerty";
+        assert_eq!("qwerty12345", String::from(s).pad_left_str(11, padding));
+        assert_eq!("qwertyqwe12345", String::from(s).pad_left_str(14, padding));
+    }
+
+    #[test]
+    fn test_pad_right_str() {
+        let s = "12345";
+        let padding = "qwerty";
+        assert_eq!("12345qwerty", String::from(s).pad_right_str(11, padding));
+        assert_eq!("12345qwertyqwe", String::from(s).pad_right_str(14, padding));
+    }
+
+    #[test]
+    fn test_is_numeric() {
+        assert!(String::from("123456").is_numeric());
+        assert!(String::from("000100").is_numeric());
+        assert!(!String::from("123v56").is_numeric());
+        assert!(!String::from("-123v56").is_numeric());
+    }
+
+    #[test]
+    fn test_is_alphabetic() {
+        assert!(String::from("abcאבג").is_alphabetic());
+        assert!(String::from("literal").is_alphabetic());
+        assert!(!String::from("v1234").is_alphabetic());
+        assert!(!String::from("6v7777").is_alphabetic());
+    }
+
+    #[test]
+    fn test_is_alphanumeric() {
+        assert!(String::from("ab123cאבג").is_alphanumeric());
+        assert!(String::from("5yu32bliteral").is_alphanumeric());
+        assert!(!String::from("!@567").is_alphanumeric());
+        assert!(!String::from("<(*^*)>").is_alphanumeric());
+    }
+
+    #[test]
+    fn test_is_swap_case() {
+        let s1 = String::from("One Two Three");
+        assert_eq!("oNE tWO tHREE", s1.swap_case());
+
+        let s2 = String::from("Y SO SERIOUS???");
+        assert_eq!("y so serious???", s2.swap_case());
+
+        let s3 = String::from("משהו בעברית");
+        assert_eq!("משהו בעברית", s3.swap_case());
+    }
+
+    #[test]
+    #[cfg(feature = "unicode")]
+    fn test_word_break_indices() {
+        assert_eq!(
+            vec![6, 10],
+            String::from("hello big world").word_break_indices()
+        );
+        assert_eq!(vec![7], String::from("hello, world").word_break_indices());
+        assert!(String::from("internationalization")
+            .word_break_indices()
+            .is_empty());
+        assert!(String::from("").word_break_indices().is_empty());
+        let s = String::from("שלום עולם");
+        assert_eq!(vec![9], s.word_break_indices());
+    }
+
+    #[test]
+    fn test_justify() {
+        assert_eq!(
+            "one   two  three",
+            String::from("one two three").justify(16)
+        );
+        assert_eq!("a    b", String::from("  a b ").justify(6));
+        assert_eq!("alone", String::from("alone").justify(10));
+        assert_eq!("too long here", String::from("too long here").justify(5));
+        assert_eq!("אחת  שתיים", String::from("אחת שתיים").justify(10));
+        let cjk = String::from("汉字 汉字").justify(10);
+        assert_eq!("汉字  汉字", cjk);
+        assert_eq!(10, unicode_width::UnicodeWidthStr::width(cjk.as_str()));
+    }
+
+    #[test]
+    fn test_justify_paragraph() {
+        let s = String::from("The quick brown fox jumps over the lazy dog and keeps on running");
+        let justified = s.justify_paragraph(16);
+        let lines: Vec<&str> = justified.lines().collect();
+        assert_eq!(
+            vec![
+                "The  quick brown",
+                "fox  jumps  over",
+                "the lazy dog and",
+                "keeps on running"
+            ],
+            lines
+        );
+
+        let s = String::from("aaa bb c dd\n\nsecond paragraph here");
+        let justified = s.justify_paragraph(8);
+        assert_eq!("aaa bb c\ndd\n\nsecond\nparagraph\nhere", justified);
+        let justified = s.justify_paragraph(7);
+        assert_eq!("aaa  bb\nc dd\n\nsecond\nparagraph\nhere", justified);
+
+        let long = String::from("a extraordinarily b");
+        assert_eq!("a\nextraordinarily\nb", long.justify_paragraph(5));
+
+        let cjk = String::from("汉字 汉字 汉字");
+        assert_eq!("汉字  汉字\n汉字", cjk.justify_paragraph(10));
+    }
+
+    #[test]
+    fn test_count_by_class() {
+        let counts = String::from("Hi 42, שלום!\t\u{2014}€").count_by_class();
+        assert_eq!(
+            CharClassCounts {
+                letters: 6,
+                digits: 2,
+                whitespace: 3,
+                punctuation: 3,
+                other: 1,
+            },
+            counts
+        );
+        assert_eq!(CharClassCounts::default(), String::new().count_by_class());
+    }
+
+    #[test]
+    fn test_truncate_at_word() {
+        let s = String::from("The quick brown fox");
+        assert_eq!("The quick…", s.truncate_at_word(14));
+        assert_eq!("The quick…", s.truncate_at_word(11));
+        assert_eq!("The…", s.truncate_at_word(9));
+        assert_eq!("The quick brown fox", s.truncate_at_word(19));
+        let long = String::from("Supercalifragilistic word");
+        assert_eq!("Super…", long.truncate_at_word(6));
+        assert_eq!("", long.truncate_at_word(0));
+    }
+
+    #[test]
+    fn test_contains_ci() {
+        let s = String::from("Hello World");
+        assert!(s.contains_ci("WORLD"));
+        assert!(s.contains_ci("lo wo"));
+        assert!(s.contains_ci(""));
+        assert!(!s.contains_ci("worlds"));
+        assert!(String::from("İSTANBUL").contains_ci("i\u{307}stanbul"));
+        assert!(String::from("\u{212A}elvin").contains_ci("KELVIN"));
+        assert!(String::from("ΣΊΣΥΦΟΣ").contains_ci("σίσυφος"));
+        assert!(!String::from("İstanbul").contains_ci("i"));
+        assert!(!String::from("istanbul").contains_ci("İ"));
+        assert!(String::from("i\u{307}stanbul").contains_ci("İ"));
+    }
+
+    #[test]
+    fn test_replace_ci() {
+        let s = String::from("HELLO world, hello there. Hello!");
+        assert_eq!("bye world, bye there. bye!", s.replace_ci("hello", "bye"));
+        assert_eq!(s, s.replace_ci("", "bye"));
+        assert_eq!(
+            "אבג x אבג",
+            String::from("אבג ABC אבג").replace_ci("abc", "x")
+        );
+        assert_eq!("--", String::from("ΣΣ").replace_ci("σ", "-"));
+        assert_eq!("İstanbul", String::from("İstanbul").replace_ci("i", "-"));
+    }
+
+    #[test]
+    fn test_starts_with_ci() {
+        let s = String::from("README.TXT");
+        assert!(s.starts_with_ci("read"));
+        assert!(s.starts_with_ci(""));
+        assert!(!s.starts_with_ci("me"));
+        assert!(String::from("ÉCOLE").starts_with_ci("éc"));
+        assert!(String::from("Straße").starts_with_ci("STRA"));
+        assert!(!String::from("İstanbul").starts_with_ci("i"));
+    }
+
+    #[test]
+    fn test_ends_with_ci() {
+        let s = String::from("README.TXT");
+        assert!(s.ends_with_ci(".txt"));
+        assert!(String::from("notes.txt").ends_with_ci(".TXT"));
+        assert!(s.ends_with_ci(""));
+        assert!(!s.ends_with_ci(".md"));
+        assert!(String::from("ΟΔΟΣ").ends_with_ci("οσ"));
+        assert!(!String::from("txt").ends_with_ci(".txt"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_distinct_char_count() {
+        assert_eq!(3, String::from("aabbbcaa").distinct_char_count());
+        assert_eq!(6, String::from("abcאבג").distinct_char_count());
+        assert_eq!(1, String::from("zzzz").distinct_char_count());
+        assert_eq!(0, String::new().distinct_char_count());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_has_duplicate_chars() {
+        assert!(String::from("hello").has_duplicate_chars());
+        assert!(String::from("אבגא").has_duplicate_chars());
+        assert!(!String::from("world").has_duplicate_chars());
+        assert!(!String::new().has_duplicate_chars());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_is_pangram() {
+        assert!(String::from("The quick brown fox jumps over the lazy dog").is_pangram());
+        assert!(String::from("PACK MY BOX WITH FIVE DOZEN LIQUOR JUGS").is_pangram());
+        assert!(!String::from("The quick brown fox jumps over the dog").is_pangram());
+        assert!(!String::new().is_pangram());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_is_pangram_for() {
+        let hebrew = ['א', 'ב', 'ג', 'ד'];
+        assert!(String::from("דג בא").is_pangram_for(&hebrew));
+        assert!(!String::from("גב").is_pangram_for(&hebrew));
+        assert!(String::from("abc").is_pangram_for(&['A', 'C']));
+        assert!(String::from("anything").is_pangram_for(&[]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_missing_letters() {
+        let s = String::from("The quick brown fox jumps over the dog");
+        assert_eq!(vec!['a', 'l', 'y', 'z'], s.missing_letters());
+        let pangram = String::from("The Quick Brown Fox Jumps Over The Lazy Dog");
+        assert!(pangram.missing_letters().is_empty());
+        assert_eq!(26, String::new().missing_letters().len());
+    }
+
+    #[test]
+    fn test_class_runs() {
+        let runs = String::from("abc123 !").class_runs();
+        assert_eq!(
+            vec![
+                (CharClass::Letter, String::from("abc")),
+                (CharClass::Digit, String::from("123")),
+                (CharClass::Whitespace, String::from(" ")),
+                (CharClass::Punctuation, String::from("!")),
+            ],
+            runs
+        );
+        let runs = String::from("x=>שלום").class_runs();
+        assert_eq!(3, runs.len());
+        assert_eq!((CharClass::Punctuation, String::from("=>")), runs[1]);
+        assert!(String::new().class_runs().is_empty());
+    }
+
+    #[test]
+    fn test_split_alpha_numeric() {
+        assert_eq!(
+            vec!["abc", "123", "def"],
+            String::from("abc123def").split_alpha_numeric()
+        );
+        assert_eq!(
+            vec!["SKU", "42", "X", "7"],
+            String::from("SKU-42X7").split_alpha_numeric()
+        );
+        assert_eq!(
+            vec!["letters"],
+            String::from("letters").split_alpha_numeric()
+        );
+        assert_eq!(vec!["2020"], String::from("2020").split_alpha_numeric());
+        assert!(String::from("--").split_alpha_numeric().is_empty());
+    }
+
+    #[test]
+    fn test_version_cmp() {
+        assert_eq!(
+            Ordering::Greater,
+            String::from("1.10.0").version_cmp("1.9.0")
+        );
+        assert_eq!(Ordering::Less, String::from("1.9").version_cmp("1.10"));
+        assert_eq!(Ordering::Equal, String::from("1.2").version_cmp("1.2.0.0"));
+        assert_eq!(Ordering::Less, String::from("1.2").version_cmp("1.2.1"));
+        assert_eq!(Ordering::Equal, String::from("01.2").version_cmp("1.2"));
+        assert_eq!(
+            Ordering::Less,
+            String::from("1.0.alpha").version_cmp("1.0.beta")
+        );
+    }
+
+    #[test]
+    fn test_redact() {
+        let log = String::from("user=admin token=abc123 retry token=abc");
+        assert_eq!(
+            "user=*** token=*** retry token=***",
+            log.redact(&["admin", "abc", "abc123"], "***")
+        );
+        assert_eq!(
+            "[x]-[x]",
+            String::from("secretsecret-secret").redact(&["secretsecret", "secret"], "[x]")
+        );
+        assert_eq!(log, log.redact(&[], "***"));
+        assert_eq!(log, log.redact(&[""], "***"));
+    }
+
+    #[test]
+    fn test_mask_email() {
+        assert_eq!(
+            "j******e@example.com",
+            String::from("john.doe@example.com").mask_email()
+        );
+        assert_eq!(
+            "b*b@example.com",
+            String::from("bob@example.com").mask_email()
+        );
+        assert_eq!(
+            "a*@example.com",
+            String::from("ab@example.com").mask_email()
+        );
+        assert_eq!("*@example.com", String::from("a@example.com").mask_email());
+        assert_eq!("not an email", String::from("not an email").mask_email());
+        assert_eq!("@example.com", String::from("@example.com").mask_email());
+        assert_eq!("a@b@c.com", String::from("a@b@c.com").mask_email());
+        assert_eq!(
+            "john@localhost",
+            String::from("john@localhost").mask_email()
+        );
+    }
+
+    #[test]
+    fn test_humanize_filename() {
+        assert_eq!(
+            "My Cool File",
+            String::from("my_cool_file.txt").humanize_filename()
+        );
+        assert_eq!(
+            "Annual Report 2020",
+            String::from("annual-report--2020.tar").humanize_filename()
+        );
+        assert_eq!(
+            "Readme",
+            String::from("/home/user/README.md").humanize_filename()
+        );
+        assert_eq!(
+            "Photo Album",
+            String::from("C:\\pics\\photo_album").humanize_filename()
+        );
+        assert_eq!(".bashrc", String::from(".bashrc").humanize_filename());
+        assert_eq!("", String::from("dir/").humanize_filename());
+    }
+
+    #[test]
+    fn test_fold_for_search() {
+        let key = String::from("cafe munchen");
+        assert_eq!(key, String::from("Café  Münchën").fold_for_search());
+        assert_eq!(key, String::from(" CAFÉ\tMÜNCHEN ").fold_for_search());
+        assert_eq!(
+            key,
+            String::from("cafe\u{301} mu\u{308}nchen").fold_for_search()
+        );
+        assert_eq!(
+            "strasse lodz",
+            String::from("Straße Łódź").fold_for_search()
+        );
+        assert_eq!("שלום עולם", String::from("שלום   עולם").fold_for_search());
+    }
+
+    #[test]
+    #[cfg(feature = "unicode")]
+    fn test_emoji_list() {
+        let s = String::from("Hi 👋🏽! Family: 👨‍👩‍👧 flag 🇮🇱 and ❤️ or 1️⃣.");
+        assert_eq!(vec!["👋🏽", "👨‍👩‍👧", "🇮🇱", "❤️", "1️⃣"], s.emoji_list());
+        assert!(String::from("plain text, 123 ©").emoji_list().is_empty());
+        assert_eq!(
+            vec!["⌛", "⏩", "⬆", "⭐"],
+            String::from("Press ⌘ or ⌛ → ⏩ ⬆ ⭐").emoji_list()
+        );
+        assert!(String::from("⌘ ⌥ ⎋ ⏎ ⌀ ⬌ ⬡ ⭢").emoji_list().is_empty());
+        assert!(String::new().emoji_list().is_empty());
+    }
+
+    #[test]
+    fn test_to_halfwidth() {
+        assert_eq!("ABC123", String::from("ＡＢＣ１２３").to_halfwidth());
+        assert_eq!(
+            "hello, world!",
+            String::from("ｈｅｌｌｏ，\u{3000}ｗｏｒｌｄ！").to_halfwidth()
+        );
+        assert_eq!(
+            "東京 abc",
+            String::from("東京\u{3000}ａｂｃ").to_halfwidth()
+        );
+    }
+
+    #[test]
+    fn test_to_fullwidth() {
+        assert_eq!("ＡＢＣ１２３", String::from("ABC123").to_fullwidth());
+        assert_eq!("ｈｉ\u{3000}～", String::from("hi ~").to_fullwidth());
+        let s = String::from("Mixed 東京 text!");
+        assert_eq!(s, s.to_fullwidth().to_halfwidth());
+    }
+
+    #[test]
+    fn test_hiragana_to_katakana() {
+        assert_eq!("ヒラガナ", String::from("ひらがな").hiragana_to_katakana());
+        assert_eq!(
+            "トウキョウ 東京 Tokyo",
+            String::from("とうきょう 東京 Tokyo").hiragana_to_katakana()
+        );
+        assert_eq!("カタカナ", String::from("カタカナ").hiragana_to_katakana());
+    }
+
+    #[test]
+    fn test_katakana_to_hiragana() {
+        assert_eq!("かたかな", String::from("カタカナ").katakana_to_hiragana());
+        assert_eq!("ー", String::from("ー").katakana_to_hiragana());
+        let s = String::from("すし");
+        assert_eq!(s, s.hiragana_to_katakana().katakana_to_hiragana());
+    }
+
+    #[test]
+    #[cfg(feature = "unicode")]
+    fn test_sentence_spans() {
+        let s = String::from("Hello there. How are you?  Fine! שלום.");
+        let spans = s.sentence_spans();
+        let sentences: Vec<&str> = spans.iter().map(|&(start, end)| &s[start..end]).collect();
+        assert_eq!(
+            vec!["Hello there.", "How are you?", "Fine!", "שלום."],
+            sentences
+        );
+        assert_eq!((0, 12), spans[0]);
+        assert!(String::from("   ").sentence_spans().is_empty());
+    }
+
+    #[test]
+    fn test_find_all() {
+        let s = String::from("a1 b22 c");
+        assert_eq!(vec![1, 4, 5], s.find_all(|c| c.is_ascii_digit()));
+        assert_eq!(vec![2, 6], s.find_all(char::is_whitespace));
+        assert_eq!(
+            vec![0, 3, 6],
+            String::from("ש ל ם").find_all(char::is_alphabetic)
+        );
+        assert!(s.find_all(|c| c == 'z').is_empty());
+    }
+
+    #[test]
+    fn test_interleave() {
+        assert_eq!("a1b2c3", String::from("abc").interleave("123"));
+        assert_eq!("a1b2cde", String::from("abcde").interleave("12"));
+        assert_eq!("a1b2345", String::from("ab").interleave("12345"));
+        assert_eq!("אaבb", String::from("אב").interleave("ab"));
+        assert_eq!("xyz", String::new().interleave("xyz"));
+    }
+
+    #[test]
+    fn test_transpose() {
+        assert_eq!("adbecf", String::from("abcdef").transpose(3));
+        assert_eq!("aebfcgd", String::from("abcdefg").transpose(4));
+        assert_eq!("abc", String::from("abc").transpose(1));
+        assert_eq!("abc", String::from("abc").transpose(10));
+        assert_eq!("abc", String::from("abc").transpose(0));
+    }
+
+    #[test]
+    fn test_transpose_lines() {
+        assert_eq!("a\td\nb\te\nc\tf", transpose_lines("a\tb\tc\nd\te\tf"));
+        assert_eq!("a\td\nb\t\nc\t", transpose_lines("a\tb\tc\nd"));
+        assert_eq!("single", transpose_lines("single"));
+        assert_eq!("", transpose_lines(""));
+    }
+
+    #[test]
+    fn test_paragraph_count() {
+        assert_eq!(
+            2,
+            String::from("first\nstill first\n\nsecond").paragraph_count()
+        );
+        assert_eq!(
+            3,
+            String::from("one\n\n\n\ntwo\n  \t\nthree").paragraph_count()
+        );
+        assert_eq!(1, String::from("\n\nonly one\n\n").paragraph_count());
+        assert_eq!(0, String::from("\n \n").paragraph_count());
+    }
+
+    #[test]
+    fn test_paragraphs() {
+        let s = String::from("  first line\nsecond line\n\n\n\nnext paragraph  \n \n\nlast\n");
+        assert_eq!(
+            vec!["first line\nsecond line", "next paragraph", "last"],
+            s.paragraphs()
+        );
+        assert!(String::from("\n\n").paragraphs().is_empty());
+    }
+
+    #[test]
+    fn test_reflow() {
+        let s = String::from("This is a\nbadly wrapped\nparagraph.\n\nAnd a\nsecond one.");
+        assert_eq!(
+            "This is a badly\nwrapped paragraph.\n\nAnd a second one.",
+            s.reflow(18)
+        );
+        assert_eq!(
+            "This is a badly wrapped paragraph.\n\nAnd a second one.",
+            s.reflow(80)
+        );
+        assert_eq!("a\nlongword\nb", String::from("a longword b").reflow(3));
+        assert_eq!("汉字 ab\n汉字", String::from("汉字\nab 汉字").reflow(7));
+    }
+
+    #[test]
+    fn test_safe_slice() {
+        let s = String::from("abcשלום");
+        assert_eq!(Some("bc"), s.safe_slice(1, 3));
+        assert_eq!(Some("של"), s.safe_slice(3, 7));
+        assert_eq!(Some(""), s.safe_slice(2, 2));
+        assert_eq!(None, s.safe_slice(3, 4));
+        assert_eq!(None, s.safe_slice(0, 100));
+        assert_eq!(None, s.safe_slice(3, 1));
+    }
+
+    #[test]
+    fn test_char_windows() {
+        let s = String::from("abcd");
+        assert_eq!(vec!["ab", "bc", "cd"], s.char_windows(2));
+        assert_eq!(vec!["abc", "bcd"], s.char_windows(3));
+        assert_eq!(vec!["abcd"], s.char_windows(4));
+        assert!(s.char_windows(5).is_empty());
+        assert!(s.char_windows(0).is_empty());
+        assert_eq!(vec!["של", "לו", "ום"], String::from("שלום").char_windows(2));
+    }
+
+    #[test]
+    fn test_rotate_left() {
+        let s = String::from("abcdef");
+        assert_eq!("cdefab", s.rotate_left(2));
+        assert_eq!("cdefab", s.rotate_left(14));
+        assert_eq!("abcdef", s.rotate_left(0));
+        assert_eq!("לוםש", String::from("שלום").rotate_left(1));
+        assert_eq!("", String::new().rotate_left(3));
+    }
+
+    #[test]
+    fn test_rotate_right() {
+        let s = String::from("abcdef");
+        assert_eq!("efabcd", s.rotate_right(2));
+        assert_eq!("efabcd", s.rotate_right(8));
+        assert_eq!("abcdef", s.rotate_right(6));
+        assert_eq!("", String::new().rotate_right(1));
+    }
+
+    #[test]
+    fn test_swap() {
+        let s = String::from("abcde");
+        assert_eq!("ebcda", s.swap(0, 4));
+        assert_eq!("adcbe", s.swap(3, 1));
+        assert_eq!("abcde", s.swap(2, 2));
+        assert_eq!("abcde", s.swap(0, 5));
+        assert_eq!("abcde", s.swap(9, 1));
+        assert_eq!("םלוש", String::from("שלום").swap(0, 3));
+    }
+
+    #[test]
+    fn test_unmatched_brackets() {
+        let balanced = String::from("fn main() { let v = [1, (2)]; }");
+        assert!(balanced.unmatched_brackets().is_empty());
+        assert_eq!(vec![('{', 4)], String::from("(a) {b").unmatched_brackets());
+        assert_eq!(vec![(')', 3)], String::from("(a))").unmatched_brackets());
+        assert_eq!(
+            vec![('(', 0), (']', 1)],
+            String::from("(]").unmatched_brackets()
+        );
+        assert_eq!(
+            vec![('(', 0), ('[', 1), (')', 3)],
+            String::from("([x)").unmatched_brackets()
+        );
+        assert_eq!(vec![('}', 4)], String::from("שם}").unmatched_brackets());
+    }
+
+    #[test]
+    fn test_is_balanced() {
+        assert!(String::from("{[()()]}").is_balanced());
+        assert!(String::from("no brackets").is_balanced());
+        assert!(String::new().is_balanced());
+        assert!(!String::from("(]").is_balanced());
+        assert!(!String::from("([)]").is_balanced());
+        assert!(!String::from("(()").is_balanced());
+        assert!(!String::from("())").is_balanced());
+    }
+
+    #[test]
+    fn test_strip_line_comments() {
+        let config = String::from(
+            "# header\nkey = 1  # one\nurl = \"http://x#y\" # link\n\nname = \"a \\\" # b\"",
+        );
+        assert_eq!(
+            "\nkey = 1\nurl = \"http://x#y\"\n\nname = \"a \\\" # b\"",
+            config.strip_line_comments("#")
+        );
+        let code = String::from("let a = 1; // first\r\n// whole line\r\nlet b = 2;");
+        assert_eq!(
+            "let a = 1;\r\n\r\nlet b = 2;",
+            code.strip_line_comments("//")
+        );
+        assert_eq!(code, code.strip_line_comments(""));
+    }
+
+    #[test]
+    fn test_unescape() {
+        let s = String::from(r#"a\nb\tc\\d\"e\'f\rg\0"#);
+        assert_eq!(Ok(String::from("a\nb\tc\\d\"e'f\rg\0")), s.unescape());
+        assert_eq!(
+            Ok(String::from("\u{07}\u{08}\u{0C}\u{0B}")),
+            String::from(r"\a\b\f\v").unescape()
+        );
+        assert_eq!(
+            Ok(String::from("A\u{1b}[")),
+            String::from(r"\x41\x1b[").unescape()
+        );
+        assert_eq!(
+            Ok(String::from("ש😀")),
+            String::from(r"\u{5e9}\u{1F600}").unescape()
+        );
+        assert_eq!(Ok(String::from("plain")), String::from("plain").unescape());
+        assert_eq!(
+            Err(UnescapeError::InvalidEscape(2)),
+            String::from(r"ab\q").unescape()
+        );
+        assert_eq!(
+            Err(UnescapeError::InvalidEscape(0)),
+            String::from(r"\x4").unescape()
+        );
+        assert_eq!(
+            Err(UnescapeError::InvalidEscape(0)),
+            String::from(r"\u{}").unescape()
+        );
+        assert_eq!(
+            Err(UnescapeError::InvalidEscape(0)),
+            String::from(r"\u{1234567}").unescape()
+        );
+        assert_eq!(
+            Err(UnescapeError::InvalidCodePoint(1)),
+            String::from(r"a\u{D800}").unescape()
+        );
+        assert_eq!(
+            Err(UnescapeError::TrailingBackslash),
+            String::from(r"abc\").unescape()
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(r"line1\nline2", String::from("line1\nline2").escape());
+        assert_eq!(r#"a\tb\r\\ \"q\""#, String::from("a\tb\r\\ \"q\"").escape());
+        assert_eq!(
+            r"bell\x07 del\x7f",
+            String::from("bell\u{07} del\u{7f}").escape()
+        );
+        assert_eq!(r"c1\u{85} שלום's", String::from("c1\u{85} שלום's").escape());
+        assert_eq!(r"\x7f\u{200b}", String::from("\u{7f}\u{200B}").escape());
+        assert_eq!(
+            r"\u{feff}a\u{2028}b\u{2029}\u{202e}cba\u{202c}\u{ad}",
+            String::from("\u{FEFF}a\u{2028}b\u{2029}\u{202E}cba\u{202C}\u{AD}").escape()
+        );
+        assert_eq!(r"👨\u{200d}👩", String::from("👨\u{200D}👩").escape());
+        let s = String::from("mixed\n\t\"\\\u{1b}[0m\u{9c}\0 \u{2066}end\u{2069}");
+        assert_eq!(Ok(s.clone()), s.escape().unescape());
+    }
+
+    #[test]
+    fn test_indentation_width() {
+        assert_eq!(4, String::from("    code").indentation_width(4));
+        assert_eq!(8, String::from("\t\tcode").indentation_width(4));
+        assert_eq!(4, String::from("  \tcode").indentation_width(4));
+        assert_eq!(10, String::from("\t  code").indentation_width(8));
+        assert_eq!(0, String::from("code\n    more").indentation_width(4));
+        assert_eq!(2, String::from("  \n    more").indentation_width(4));
+        assert_eq!(2, String::from("\t\t").indentation_width(0));
+    }
+
+    #[test]
+    fn test_detect_indent() {
+        let two = String::from("fn main() {\n  if x {\n    y();\n  }\n\n}");
+        assert_eq!(IndentStyle::Spaces(2), two.detect_indent());
+        let four = String::from("a:\n    b:\n        c\n    d");
+        assert_eq!(IndentStyle::Spaces(4), four.detect_indent());
+        let outlier = String::from("a:\n    b:\n        c\n   odd\n    d\n        e");
+        assert_eq!(IndentStyle::Spaces(4), outlier.detect_indent());
+        let threes = String::from("a\n   b\n      c\n  d");
+        assert_eq!(IndentStyle::Spaces(3), threes.detect_indent());
+        assert_eq!(
+            IndentStyle::Spaces(2),
+            String::from("a\n  b\n    c\n").detect_indent()
+        );
+        let tabs = String::from("a\n\tb\n\t\tc\n");
+        assert_eq!(IndentStyle::Tabs, tabs.detect_indent());
+        let mixed = String::from("a\n\tb\n    c");
+        assert_eq!(IndentStyle::Mixed, mixed.detect_indent());
+        assert_eq!(IndentStyle::Mixed, String::from("a\n \tb").detect_indent());
+        assert_eq!(
+            IndentStyle::Mixed,
+            String::from("flat\ntext").detect_indent()
+        );
+    }
+
+    #[test]
+    fn test_sentence_case() {
+        assert_eq!(
+            "This is loud. Very loud! Is it? Yes.",
+            String::from("THIS IS LOUD. VERY LOUD! IS IT? YES.").sentence_case()
+        );
+        assert_eq!(
+            "  Hello. World",
+            String::from("  hELLO. wORLD").sentence_case()
+        );
+        assert_eq!("123 Go. Now", String::from("123 GO. now").sentence_case());
+        assert_eq!(
+            "Éclair. Ça va",
+            String::from("ÉCLAIR. ÇA VA").sentence_case()
+        );
+        assert_eq!(
+            "I like e.g. This. Ok",
+            String::from("i like e.g. this. ok").sentence_case()
+        );
+        assert_eq!(
+            "See example.com or v1.2.3!",
+            String::from("SEE EXAMPLE.COM OR V1.2.3!").sentence_case()
+        );
+        assert_eq!(
+            "Wait...
+What",
+            String::from(
+                "wait...
+what"
+            )
+            .sentence_case()
+        );
+    }
+
+    #[test]
+    fn test_dedup_lines() {
+        let log = String::from("start\nretry\nretry\r\nretry\ndone\nretry\n");
+        assert_eq!("start\nretry\ndone\nretry\n", log.dedup_lines());
+        assert_eq!("a\nb", String::from("a\na\nb").dedup_lines());
+        assert_eq!("", String::new().dedup_lines());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_dedup_lines_global() {
+        let log = String::from("a\nb\na\nc\nb\n");
+        assert_eq!("a\nb\nc\n", log.dedup_lines_global());
+        assert_eq!(
+            "x\r\ny\r\n",
+            String::from("x\r\ny\r\nx\r\n").dedup_lines_global()
+        );
+    }
+
+    #[test]
+    fn test_sort_lines() {
+        assert_eq!(
+            "apple\nbanana\ncherry\n",
+            String::from("cherry\napple\nbanana\n").sort_lines()
+        );
+        assert_eq!("1\n10\n9", String::from("10\n9\n1").sort_lines());
+        assert_eq!("", String::new().sort_lines());
+    }
+
+    #[test]
+    fn test_sort_lines_by() {
+        let numbered = String::from("10\n9\n1\n");
+        assert_eq!(
+            "1\n9\n10\n",
+            numbered.sort_lines_by(|a, b| String::from(a).version_cmp(b))
+        );
+        let versions = String::from("1.10.0\n1.2.0\n1.9.3");
+        assert_eq!(
+            "1.10.0\n1.9.3\n1.2.0",
+            versions.sort_lines_by(|a, b| String::from(b).version_cmp(a))
+        );
+        let crlf = String::from("b\r\na\r\n");
+        assert_eq!("a\r\nb\r\n", crlf.sort_lines_by(|a, b| a.cmp(b)));
+        assert_eq!(
+            "a\nb\r\n",
+            String::from("b\r\na\n").sort_lines_by(|a, b| a.cmp(b))
+        );
+        assert_eq!(
+            "a\r\nb",
+            String::from("b\r\na").sort_lines_by(|a, b| a.cmp(b))
+        );
+    }
+
+    #[test]
+    fn test_shuffle_lines() {
+        let s = String::from("one\ntwo\nthree\nfour\nfive\nsix\n");
+        let shuffled = s.shuffle_lines(42);
+        assert_eq!(shuffled, s.shuffle_lines(42));
+        assert_ne!(s, shuffled);
+        assert!(shuffled.ends_with('\n'));
+        let mut original: Vec<&str> = s.lines().collect();
+        let mut lines: Vec<&str> = shuffled.lines().collect();
+        original.sort();
+        lines.sort();
+        assert_eq!(original, lines);
+        assert_eq!("single", String::from("single").shuffle_lines(7));
+        let crlf = s.replace('\n', "\r\n");
+        assert_eq!(shuffled.replace('\n', "\r\n"), crlf.shuffle_lines(42));
+        let unterminated = String::from("one\r\ntwo\r\nthree");
+        assert_eq!("three\r\none\r\ntwo", unterminated.shuffle_lines(4));
+    }
+
+    #[test]
+    fn test_line_gutter() {
+        let s = String::from("a\nb\nc\nd\ne\n");
+        assert_eq!("+ a\n- b\n+ c\n- d\n+ e\n", s.line_gutter(&['+', '-']));
+        assert_eq!("> a\n> b", String::from("a\nb").line_gutter(&['>']));
+        assert_eq!(
+            "+ x\n- y",
+            String::from("x\ny").line_gutter(&['+', '-', ' '])
+        );
+        assert_eq!(s, s.line_gutter(&[]));
+    }
+
+    #[test]
+    fn test_strip_bom() {
+        assert_eq!("key,value", String::from("\u{FEFF}key,value").strip_bom());
+        assert_eq!("key,value", String::from("key,value").strip_bom());
+        assert_eq!("a\u{FEFF}b", String::from("a\u{FEFF}b").strip_bom());
+    }
+
+    #[test]
+    fn test_has_bom() {
+        assert!(String::from("\u{FEFF}text").has_bom());
+        assert!(!String::from("text").has_bom());
+        assert!(!String::from("te\u{FEFF}xt").has_bom());
+    }
+
+    #[test]
+    fn test_is_json_string_content() {
+        assert!(String::from("plain text שלום").is_json_string_content());
+        assert!(String::from(r#"quote \" slash \/ \\ \n\t é"#).is_json_string_content());
+        assert!(String::new().is_json_string_content());
+        assert!(!String::from("line\nbreak").is_json_string_content());
+        assert!(!String::from("bare \" quote").is_json_string_content());
+        assert!(!String::from(r"bad \x41 escape").is_json_string_content());
+        assert!(!String::from(r"short \u12").is_json_string_content());
+        assert!(!String::from(r"ends with \").is_json_string_content());
+    }
+
+    #[test]
+    fn test_format_phone_us() {
+        let formatted = Some(String::from("(123) 456-7890"));
+        assert_eq!(formatted, String::from("1234567890").format_phone_us());
+        assert_eq!(formatted, String::from("123.456.7890").format_phone_us());
+        assert_eq!(
+            formatted,
+            String::from("(123) 456 - 7890").format_phone_us()
+        );
+        assert_eq!(None, String::from("123456789").format_phone_us());
+        assert_eq!(None, String::from("+1 123 456 7890").format_phone_us());
+        assert_eq!(None, String::from("no digits").format_phone_us());
+    }
+
+    #[test]
+    fn test_digits_only() {
+        assert_eq!("15551234", String::from("+1 (555) 12-34").digits_only());
+        assert_eq!("2020", String::from("v2020-rc").digits_only());
+        assert_eq!("", String::from("no digits here").digits_only());
+        assert_eq!("", String::from("١٢٣").digits_only());
+    }
+
+    #[test]
+    fn test_is_valid_luhn() {
+        assert!(String::from("4539 1488 0343 6467").is_valid_luhn());
+        assert!(String::from("79927398713").is_valid_luhn());
+        assert!(String::from("4111-1111-1111-1111").is_valid_luhn());
+        assert!(!String::from("4539 1488 0343 6468").is_valid_luhn());
+        assert!(!String::from("79927398710").is_valid_luhn());
+        assert!(!String::from("0").is_valid_luhn());
+        assert!(!String::new().is_valid_luhn());
+    }
+
+    #[test]
+    fn test_luhn_check_digit() {
+        assert_eq!(Some(3), String::from("7992739871").luhn_check_digit());
+        assert_eq!(
+            Some(7),
+            String::from("4539 1488 0343 646").luhn_check_digit()
+        );
+        assert_eq!(None, String::from("no digits").luhn_check_digit());
+        for payload in &["1", "42", "12345", "411111111111111", "000"] {
+            let digit = String::from(*payload).luhn_check_digit().unwrap();
+            assert!(format!("{}{}", payload, digit).is_valid_luhn());
+        }
+    }
+
+    #[test]
+    fn test_to_base32() {
+        assert_eq!("", String::new().to_base32());
+        assert_eq!("MY======", String::from("f").to_base32());
+        assert_eq!("MZXQ====", String::from("fo").to_base32());
+        assert_eq!("MZXW6===", String::from("foo").to_base32());
+        assert_eq!("MZXW6YQ=", String::from("foob").to_base32());
+        assert_eq!("MZXW6YTB", String::from("fooba").to_base32());
+        assert_eq!("MZXW6YTBOI======", String::from("foobar").to_base32());
+    }
+
+    #[test]
+    fn test_from_base32() {
+        assert_eq!(
+            Ok(String::from("foobar")),
+            String::from("MZXW6YTBOI======").from_base32()
+        );
+        assert_eq!(
+            Ok(String::from("foobar")),
+            String::from("mzxw6ytboi").from_base32()
+        );
+        assert_eq!(Ok(String::new()), String::new().from_base32());
+        let s = String::from("שלום, TOTP secret!");
+        assert_eq!(Ok(s.clone()), s.to_base32().from_base32());
+        assert_eq!(
+            Err(DecodeError::InvalidCharacter('1')),
+            String::from("MZ1W6===").from_base32()
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidLength),
+            String::from("MZX=====").from_base32()
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidPadding),
+            String::from("MY=====").from_base32()
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidPadding),
+            String::from("MZXW6YTB========").from_base32()
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidPadding),
+            String::from("MZ======").from_base32()
+        );
+        assert_eq!(
+            Ok(String::from("f")),
+            String::from("MY======").from_base32()
+        );
+        assert_eq!(Ok(String::from("f")), String::from("my").from_base32());
+        assert_eq!(
+            Err(DecodeError::InvalidUtf8),
+            String::from("74======").from_base32()
+        );
+    }
+
+    #[test]
+    fn test_to_base58() {
+        assert_eq!("", String::new().to_base58());
+        assert_eq!(
+            "2NEpo7TZRRrLZSi2U",
+            String::from("Hello World!").to_base58()
+        );
+        assert_eq!("11ZiCa", String::from("\0\0abc").to_base58());
+        assert_eq!("1", String::from("\0").to_base58());
+    }
+
+    #[test]
+    fn test_from_base58() {
+        assert_eq!(
+            Ok(String::from("Hello World!")),
+            String::from("2NEpo7TZRRrLZSi2U").from_base58()
+        );
+        assert_eq!(
+            Ok(String::from("\0\0abc")),
+            String::from("11ZiCa").from_base58()
+        );
+        assert_eq!(Ok(String::new()), String::new().from_base58());
+        for s in &["שלום", "\0", "\0\0\0x", "base58 round trip"] {
+            let s = String::from(*s);
+            assert_eq!(Ok(s.clone()), s.to_base58().from_base58());
+        }
+        assert_eq!(
+            Err(DecodeError::InvalidCharacter('0')),
+            String::from("2NEp0").from_base58()
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidCharacter('l')),
+            String::from("l").from_base58()
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidUtf8),
+            String::from("5Q").from_base58()
+        );
+    }
+
+    #[test]
+    fn test_alpha_ratio() {
+        assert_eq!(1.0, String::from("letters").alpha_ratio());
+        assert_eq!(1.0, String::from("אבג").alpha_ratio());
+        assert_eq!(0.5, String::from("ab12").alpha_ratio());
+        assert_eq!(0.0, String::from("#$%1").alpha_ratio());
+        assert_eq!(0.0, String::new().alpha_ratio());
+    }
+
+    #[test]
+    fn test_is_shouting() {
+        assert!(String::from("WHY IS THIS BROKEN?!").is_shouting(0.7));
+        assert!(String::from("STOP doing that").is_shouting(0.3));
+        assert!(!String::from("Hello there, how are you?").is_shouting(0.7));
+        assert!(!String::from("OK").is_shouting(0.5));
+        assert!(!String::from("שלום ABC").is_shouting(0.5));
+        assert!(!String::new().is_shouting(0.0));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_unique_slug() {
+        let mut existing = HashSet::new();
+        assert_eq!("hello-world", unique_slug("Hello, World!", &existing));
+        assert_eq!("cafe-munchen", unique_slug("  Café München ", &existing));
+        existing.insert(String::from("hello-world"));
+        assert_eq!("hello-world-2", unique_slug("Hello World", &existing));
+        existing.insert(String::from("hello-world-2"));
+        existing.insert(String::from("hello-world-3"));
+        assert_eq!("hello-world-4", unique_slug("hello world", &existing));
+    }
+
+    #[test]
+    fn test_split_on_any() {
+        let s = String::from("a,b;c,,;d");
+        assert_eq!(vec!["a", "b", "c", "d"], s.split_on_any(&[',', ';']));
+        assert_eq!(
+            vec!["a", "b;c", "d"],
+            String::from(",a,b;c,d,").split_on_any(&[','])
+        );
+        assert_eq!(vec!["a,b;c,,;d"], s.split_on_any(&[]));
+        assert!(String::from(",;").split_on_any(&[',', ';']).is_empty());
+    }
+
+    #[test]
+    fn test_split_at_indices() {
+        let record = String::from("0042JOHN  NY");
+        assert_eq!(
+            vec!["0042", "JOHN  ", "NY"],
+            record.split_at_indices(&[4, 10])
+        );
+        assert_eq!(vec!["abc"], String::from("abc").split_at_indices(&[]));
+        assert_eq!(vec!["", "abc"], String::from("abc").split_at_indices(&[0]));
+        assert_eq!(
+            vec!["abc", "", ""],
+            String::from("abc").split_at_indices(&[5, 9])
+        );
+        assert_eq!(
+            vec!["ab", "", "c"],
+            String::from("abc").split_at_indices(&[2, 1])
+        );
+        assert_eq!(
+            vec!["של", "ום"],
+            String::from("שלום").split_at_indices(&[2])
+        );
+    }
+
+    #[test]
+    fn test_parse_fixed_width() {
+        let record = String::from("0042JOHN  NY");
+        assert_eq!(
+            vec!["0042", "JOHN", "NY"],
+            record.parse_fixed_width(&[4, 6, 2])
+        );
+        assert_eq!(
+            vec!["0042", "JOHN", "NY"],
+            record.parse_fixed_width(&[4, 6])
+        );
+        assert_eq!(
+            vec!["0042", "JOHN  NY", ""],
+            record.parse_fixed_width(&[4, 10, 3])
+        );
+        assert_eq!(vec!["0042JOHN  NY"], record.parse_fixed_width(&[]));
+    }
+
+    #[test]
+    fn test_to_fixed_width() {
+        let row = String::from("0042\tJOHN\tNY");
+        assert_eq!("0042JOHN  NY", row.to_fixed_width(&[4, 6, 2]));
+        assert_eq!("00JO", row.to_fixed_width(&[2, 2]));
+        assert_eq!("0042JOHN  NY   ", row.to_fixed_width(&[4, 6, 2, 3]));
+        let parsed = row.to_fixed_width(&[4, 6, 2]).parse_fixed_width(&[4, 6, 2]);
+        assert_eq!(row, parsed.join("\t"));
+        assert_eq!("שלום  ", String::from("שלום").to_fixed_width(&[6]));
+    }
+
+    #[test]
+    fn test_expand_ranges() {
+        assert_eq!("abc012", String::from("a-c0-2").expand_ranges());
+        assert_eq!("a-c", String::from("a\\-c").expand_ranges());
+        assert_eq!("xyz_-", String::from("x-z_-").expand_ranges());
+        assert_eq!("-ab", String::from("-a-b").expand_ranges());
+        assert_eq!("z-a", String::from("z-a").expand_ranges());
+        assert_eq!("אבג", String::from("א-ג").expand_ranges());
+        assert_eq!("+,-", String::from("+-\\-").expand_ranges());
+        assert_eq!("-./", String::from("\\--/").expand_ranges());
+        assert_eq!("\\]^", String::from("\\\\-^").expand_ranges());
+        assert_eq!("a\\", String::from("a\\").expand_ranges());
+    }
+
+    #[test]
+    fn test_contract_ranges() {
+        assert_eq!("a-e", String::from("abcde").contract_ranges());
+        assert_eq!(
+            "a-c0-9xz",
+            String::from("abc0123456789xz").contract_ranges()
+        );
+        assert_eq!("abxy", String::from("abxy").contract_ranges());
+        assert_eq!("\\-a-c", String::from("-abc").contract_ranges());
+        assert_eq!("א-ד", String::from("אבגד").contract_ranges());
+        for original in ["a-cdef\\xyz", "+,-", "-./", "\\]^", ",-.", "x+,-y"] {
+            let s = String::from(original);
+            assert_eq!(s, s.contract_ranges().expand_ranges());
+        }
+    }
+
+    #[test]
+    fn test_longest_repeated_substring() {
+        assert_eq!(
+            "abc",
+            String::from("xabcyabcz").longest_repeated_substring()
+        );
+        assert_eq!("ana", String::from("banana").longest_repeated_substring());
+        assert_eq!("aaa", String::from("aaaa").longest_repeated_substring());
+        assert_eq!("של", String::from("שלום של").longest_repeated_substring());
+        assert_eq!("", String::from("abcdef").longest_repeated_substring());
+        assert_eq!("", String::new().longest_repeated_substring());
+    }
+
+    #[test]
+    fn test_is_subsequence() {
+        assert!(String::from("mlrs").is_subsequence("src/main/lib.rs"));
+        assert!(String::from("abc").is_subsequence("abc"));
+        assert!(String::from("שם").is_subsequence("שלום"));
+        assert!(String::new().is_subsequence("anything"));
+        assert!(String::new().is_subsequence(""));
+        assert!(!String::from("cba").is_subsequence("abc"));
+        assert!(!String::from("aa").is_subsequence("a"));
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        let consecutive = String::from("main.rs").fuzzy_score("main").unwrap();
+        let scattered = String::from("my_animal_notes.rs")
+            .fuzzy_score("main")
+            .unwrap();
+        assert!(consecutive > scattered);
+
+        let boundary = String::from("file_manager").fuzzy_score("fm").unwrap();
+        let inner = String::from("filmmaker").fuzzy_score("fm").unwrap();
+        assert!(boundary > inner);
+
+        let camel = String::from("FileManager").fuzzy_score("fm").unwrap();
+        assert!(camel > inner);
+
+        assert!(String::from("README").fuzzy_score("readme").is_some());
+        assert_eq!(Some(0), String::from("anything").fuzzy_score(""));
+        assert_eq!(None, String::from("main.rs").fuzzy_score("mainx"));
+        assert_eq!(None, String::from("abc").fuzzy_score("cba"));
+    }
+
+    #[test]
+    fn test_redact_digits() {
+        assert_eq!("id-#####", String::from("id-12345").redact_digits('#'));
+        assert_eq!(
+            "card ****-****, exp **/**",
+            String::from("card 1234-5678, exp 01/27").redact_digits('*')
+        );
+        assert_eq!("no digits", String::from("no digits").redact_digits('#'));
+        assert_eq!("שלום x", String::from("שלום 7").redact_digits('x'));
+    }
+
+    #[test]
+    fn test_search_tokens() {
+        assert_eq!(
+            vec!["get", "http", "response", "now"],
+            String::from("getHTTPResponse-now").search_tokens()
+        );
+        assert_eq!(
+            vec!["parse", "json", "v2", "file", "name"],
+            String::from("parseJSON v2, file_name!").search_tokens()
+        );
+        assert_eq!(
+            vec!["שלום", "world"],
+            String::from("שלום World").search_tokens()
+        );
+        assert!(String::from(" -- ").search_tokens().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_search_tokens_unique() {
+        assert_eq!(
+            vec!["user", "id", "name"],
+            String::from("userId user_name UserID").search_tokens_unique()
+        );
+    }
+
+    #[test]
+    fn test_mirror() {
+        assert_eq!("[)cba(]", String::from("[)abc(]").mirror());
+        assert_eq!("{x} <y>", String::from("<y> {x}").mirror());
+        assert_eq!(" \\\n/ ", String::from("/ \n \\").mirror());
+        assert_eq!("  /\\\n \\  /", String::from("/\\  \n\\  / ").mirror());
+        assert_eq!("ba\r\ndc\r\n", String::from("ab\r\ncd\r\n").mirror());
+        assert_eq!("a\n\n)b", String::from("a\n\nb(").mirror());
+        assert_eq!("", String::new().mirror());
+    }
+
+    #[test]
+    fn test_vertical() {
+        assert_eq!("w\no\nr\nd", String::from("word").vertical());
+        assert_eq!("a c\nb d\n  e", String::from("ab\ncde").vertical());
+        assert_eq!("a d\nb\nc", String::from("abc\nd").vertical());
+        assert_eq!("", String::new().vertical());
+    }
+
+    #[test]
+    fn test_banner() {
+        let banner = String::from("Hi 1").banner();
+        let rows: Vec<&str> = banner.lines().collect();
+        assert_eq!(5, rows.len());
+        assert!(rows.iter().all(|row| row.chars().count() == 23));
+        assert_eq!("#   # #####         #  ", rows[0]);
+        assert_eq!("#   #   #          ##  ", rows[1]);
+        assert_eq!(
+            " ### \n#   #\n#####\n#   #\n#   #",
+            String::from("a").banner()
+        );
+        assert_eq!(
+            "     \n     \n     \n     \n     ",
+            String::from("?").banner()
+        );
+    }
+
+    #[test]
+    fn test_count_char() {
+        assert_eq!(3, String::from("banana").count_char('a'));
+        assert_eq!(0, String::from("banana").count_char('z'));
+        assert_eq!(2, String::from("שלום שלום").count_char('ש'));
+        assert_eq!(0, String::new().count_char('a'));
+    }
+
+    #[test]
+    fn test_first_difference() {
+        assert_eq!(Some(2), String::from("abcd").first_difference("abxd"));
+        assert_eq!(Some(0), String::from("abc").first_difference("xbc"));
+        assert_eq!(None, String::from("same").first_difference("same"));
+        assert_eq!(Some(3), String::from("abc").first_difference("abcdef"));
+        assert_eq!(Some(3), String::from("abcdef").first_difference("abc"));
+        assert_eq!(Some(1), String::from("שלום").first_difference("שמש"));
+        assert_eq!(None, String::new().first_difference(""));
+    }
+
+    #[test]
+    fn test_common_prefix_len() {
+        assert_eq!(
+            5,
+            common_prefix_len(&["interview", "internet", "interval", "intern"])
+        );
+        assert_eq!(0, common_prefix_len(&["abc", "xyz"]));
+        assert_eq!(5, common_prefix_len(&["alone"]));
+        assert_eq!(0, common_prefix_len(&[]));
+        assert_eq!(0, common_prefix_len(&["abc", ""]));
+        assert_eq!(2, common_prefix_len(&["שלום", "שלט"]));
+    }
+
+    #[test]
+    fn test_strip_smart_quotes() {
+        let s = String::from("“It’s done—finally…” she said, ‘pages 3–5’.");
+        assert_eq!(
+            "\"It's done--finally...\" she said, 'pages 3-5'.",
+            s.strip_smart_quotes()
+        );
+        assert_eq!(
+            "plain \"text\"",
+            String::from("plain \"text\"").strip_smart_quotes()
+        );
+    }
+
+    #[test]
+    fn test_to_smart_quotes() {
+        assert_eq!(
+            "“Hello,” she said. “It’s ‘fine’—really.”",
+            String::from("\"Hello,\" she said. \"It's 'fine'--really.\"").to_smart_quotes()
+        );
+        assert_eq!(
+            "don’t (“quoted”)",
+            String::from("don't (\"quoted\")").to_smart_quotes()
+        );
+        assert_eq!("a - b", String::from("a - b").to_smart_quotes());
+        let s = String::from("\"It's\"--done");
+        assert_eq!(s, s.to_smart_quotes().strip_smart_quotes());
+    }
+
+    #[test]
+    fn test_word_at() {
+        let s = String::from("select the word");
+        assert_eq!(Some("select"), s.word_at(0));
+        assert_eq!(Some("the"), s.word_at(8));
+        assert_eq!(Some("word"), s.word_at(14));
+        assert_eq!(None, s.word_at(6));
+        assert_eq!(None, s.word_at(15));
+        assert_eq!(Some("עולם"), String::from("שלום עולם").word_at(6));
+    }
+
+    #[test]
+    fn test_replace_range() {
+        let s = String::from("hello world");
+        assert_eq!("hello there", ExtString::replace_range(&s, 6, 11, "there"));
+        assert_eq!("he--o world", ExtString::replace_range(&s, 2, 4, "--"));
+        assert_eq!("hello, world", ExtString::replace_range(&s, 5, 5, ","));
+        assert_eq!("hello!", ExtString::replace_range(&s, 5, 100, "!"));
+        assert_eq!("hello world!", ExtString::replace_range(&s, 50, 60, "!"));
+        assert_eq!("hello_ world", ExtString::replace_range(&s, 5, 2, "_"));
+        assert_eq!(
+            "שXם",
+            ExtString::replace_range(&String::from("שלום"), 1, 3, "X")
+        );
+    }
+
+    #[test]
+    fn test_measured_string() {
+        let s = String::from("汉字e\u{301}");
+        let measured = MeasuredString::new(s.as_str());
+        assert_eq!(s, measured.as_str());
+        assert_eq!(s.chars().count(), measured.char_count());
+        #[cfg(feature = "unicode")]
+        assert_eq!(s.graphemes(true).count(), measured.grapheme_count());
+        assert_eq!(UnicodeWidthStr::width(s.as_str()), measured.display_width());
+        assert_eq!(4, measured.char_count());
+        #[cfg(feature = "unicode")]
+        assert_eq!(3, measured.grapheme_count());
+        #[cfg(not(feature = "unicode"))]
+        assert_eq!(4, measured.grapheme_count());
+        assert_eq!(5, measured.display_width());
+        assert_eq!(s.pad_left(8, '*'), measured.pad_left(8, '*'));
+        assert_eq!(s.pad_right(8, '*'), measured.pad_right(8, '*'));
+        assert_eq!(s.pad_right(3, '*'), measured.pad_right(3, '*'));
+
+        let owned = MeasuredString::new(String::from("owned"));
+        assert_eq!(5, owned.display_width());
+    }
+
+    #[test]
+    fn test_measured_string_large_input() {
+        let s = "The quick brown fox 汉字 जल्दी ".repeat(10_000);
+        let measured = MeasuredString::new(s.as_str());
+        assert_eq!(s.chars().count(), measured.char_count());
+        #[cfg(feature = "unicode")]
+        assert_eq!(s.graphemes(true).count(), measured.grapheme_count());
+        let width = measured.char_count() + 10;
+        let expected = String::from(s.as_str()).pad_right(width, '.');
+        for _ in 0..100 {
+            assert_eq!(expected.len(), measured.pad_right(width, '.').len());
+        }
+        assert_eq!(expected, measured.pad_right(width, '.'));
+    }
+
+    #[test]
+    fn test_padder() {
+        let right = Padder::new(8, '.', Alignment::Right);
+        let left = Padder::new(8, '.', Alignment::Left);
+        for cell in &["", "abc", "שלום", "exactly8", "much too long"] {
+            let s = String::from(*cell);
+            assert_eq!(s.pad_left(8, '.'), right.pad(cell));
+            assert_eq!(s.pad_right(8, '.'), left.pad(cell));
+        }
+        let center = Padder::new(7, '-', Alignment::Center);
+        assert_eq!("--ab---", center.pad("ab"));
+        assert_eq!("--abc--", center.pad("abc"));
+    }
+
+    #[test]
+    fn test_padder_pad_into() {
+        let padder = Padder::new(5, ' ', Alignment::Left);
+        let mut buf = String::from("|");
+        for cell in &["a", "bb", "ccc"] {
+            padder.pad_into(cell, &mut buf);
+            buf.push('|');
+        }
+        assert_eq!("|a    |bb   |ccc  |", buf);
+        let mut buf = String::new();
+        Padder::new(4, '0', Alignment::Right).pad_into("42", &mut buf);
+        assert_eq!(String::from("42").pad_left(4, '0'), buf);
+    }
+
+    #[test]
+    fn test_transform_all() {
+        let names = vec![
+            String::from("ada lovelace"),
+            String::from("john q. public"),
+            String::from("GRACE HOPPER"),
+        ];
+        assert_eq!(
+            vec!["Ada Lovelace", "John Q. Public", "Grace Hopper"],
+            transform_all(names, |s| s.title_case_except(&[]))
+        );
+        let sentences = vec![String::from("HELLO. WORLD!"), String::from("")];
+        assert_eq!(
+            vec!["Hello. World!", ""],
+            transform_all(sentences, String::sentence_case)
+        );
+        assert!(transform_all(Vec::new(), String::reverse).is_empty());
+        assert_eq!(
+            vec!["cba", "fed"],
+            transform_all(vec![String::from("abc"), String::from("def")], |s| s
+                .reverse())
+        );
+    }
+
+    #[test]
+    fn test_map_lines() {
+        assert_eq!(
+            "3\n0\n5\n",
+            String::from("abc\n\nhello\n").map_lines(|line| line.len().to_string())
+        );
+        assert_eq!(
+            "CBA\nfed",
+            String::from("abc\ndef").map_lines(|line| {
+                let reversed = String::from(line).reverse();
+                if line == "abc" {
+                    reversed.to_uppercase()
+                } else {
+                    reversed
+                }
+            })
+        );
+        assert_eq!("", String::new().map_lines(|line| line.to_uppercase()));
+        assert_eq!(
+            "A\r\nB\nC",
+            String::from("a\r\nb\nc").map_lines(|line| line.to_uppercase())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_par_map_lines() {
+        let text = (0..50_000)
+            .map(|i| format!("line {} of the file", i))
+            .collect::<Vec<String>>()
+            .join("\n")
+            + "\n";
+        let f = |line: &str| String::from(line).swap_case();
+        assert_eq!(text.map_lines(f), text.par_map_lines(f));
+        assert_eq!("", String::new().par_map_lines(f));
+        assert_eq!("A\nB", String::from("a\nb").par_map_lines(f));
+        let crlf = text.replace('\n', "\r\n");
+        assert_eq!(crlf.map_lines(f), crlf.par_map_lines(f));
+        assert_eq!("A\r\nB\r\n", String::from("a\r\nb\r\n").par_map_lines(f));
+    }
+
+    #[test]
+    fn test_eq_ignore_whitespace() {
+        let s = String::from("fn main() {\n    println!(\"hi\");\n}\n");
+        assert!(s.eq_ignore_whitespace("fn main(){ println!(\"hi\"); }"));
+        assert!(s.eq_ignore_whitespace("fnmain(){println!(\"hi\");}"));
+        assert!(!s.eq_ignore_whitespace("fn main() { println!(\"ho\"); }"));
+        assert!(!s.eq_ignore_whitespace("fn main() {}"));
+        assert!(String::from(" \t\n").eq_ignore_whitespace(""));
+        assert!(String::from("a\u{3000}b").eq_ignore_whitespace("ab"));
+        assert!(!String::from("ab").eq_ignore_whitespace("abc"));
+    }
+
+    #[test]
+    fn test_bucket() {
+        assert_eq!(
+            (0xaf63_dc4c_8601_ec8c_u64 % 7) as usize,
+            String::from("a").bucket(7)
+        );
+        assert_eq!(
+            (0xcbf2_9ce4_8422_2325_u64 % 10) as usize,
+            String::new().bucket(10)
+        );
+        assert_eq!(0, String::from("anything").bucket(0));
+        assert_eq!(0, String::from("anything").bucket(1));
+        let key = String::from("user:42");
+        assert_eq!(key.bucket(16), key.clone().bucket(16));
+
+        let mut counts = [0; 10];
+        for i in 0..10_000 {
+            let bucket = format!("key-{}", i).bucket(10);
+            assert!(bucket < 10);
+            counts[bucket] += 1;
+        }
+        assert!(counts.iter().all(|&count| count > 800 && count < 1200));
+    }
+
+    #[test]
+    fn test_title_case_except() {
+        let small = ["a", "of", "the", "in"];
+        assert_eq!(
+            "The Lord of the Rings",
+            String::from("THE LORD OF THE RINGS").title_case_except(&small)
+        );
+        assert_eq!(
+            "A Tale of Two Cities",
+            String::from("a tale of two cities").title_case_except(&small)
+        );
+        assert_eq!(
+            "Gone  With the\nWind",
+            String::from("gone  with THE\nwind").title_case_except(&["THE"])
+        );
+        assert_eq!(
+            "The Lord Of The Rings",
+            String::from("the lord of the rings").title_case_except(&[])
+        );
+        assert_eq!(
+            "The (of) of \"Hello,\" [World]",
+            String::from("the (of) OF \"hello,\" [world]").title_case_except(&small)
+        );
+        assert_eq!(
+            "\"The\" End of It",
+            String::from("\"the\" end of it").title_case_except(&small)
+        );
+        assert_eq!(
+            "-- The End",
+            String::from("-- the end").title_case_except(&small)
+        );
+        assert_eq!("", String::new().title_case_except(&small));
+    }
+
+    #[test]
+    fn test_word_count_opts() {
+        let s = String::from("In 2024, a well-known author wrote 3 books -- really!");
+        let defaults = WordCountOptions::default();
+        assert_eq!(9, s.word_count_opts(defaults));
+        assert_eq!(
+            7,
+            s.word_count_opts(WordCountOptions {
+                count_numbers: false,
+                ..defaults
+            })
+        );
+        assert_eq!(
+            10,
+            s.word_count_opts(WordCountOptions {
+                hyphenated_as_one: false,
+                ..defaults
+            })
+        );
+        assert_eq!(
+            6,
+            s.word_count_opts(WordCountOptions {
+                min_length: 3,
+                ..defaults
+            })
+        );
+        assert_eq!(2, String::from("don't stop").word_count_opts(defaults));
+        assert_eq!(
+            3,
+            String::from("this and/or that").word_count_opts(defaults)
+        );
+        let dotted = String::from("e.g. U.S.A. 3.14 foo@bar.com");
+        assert_eq!(4, dotted.word_count_opts(defaults));
+        assert_eq!(
+            3,
+            dotted.word_count_opts(WordCountOptions {
+                count_numbers: false,
+                ..defaults
+            })
+        );
+        assert_eq!(
+            3,
+            String::from("(self-made) 'x'").word_count_opts(WordCountOptions {
+                hyphenated_as_one: false,
+                ..defaults
+            })
+        );
+        assert_eq!(0, String::from(" -- ... ").word_count_opts(defaults));
+        assert_eq!(0, String::new().word_count_opts(defaults));
+    }
+
+    #[test]
+    fn test_strip_zero_width() {
+        assert_eq!(
+            "password",
+            String::from("pass\u{200B}word").strip_zero_width()
+        );
+        assert_eq!(
+            "abcde",
+            String::from("\u{FEFF}a\u{200B}b\u{200C}c\u{200D}d\u{2060}e").strip_zero_width()
+        );
+        assert_eq!(
+            "👨👩👧",
+            String::from("👨\u{200D}👩\u{200D}👧").strip_zero_width()
+        );
+        assert_eq!("", String::new().strip_zero_width());
+    }
+
+    #[test]
+    fn test_strip_zero_width_keep_emoji() {
+        assert_eq!(
+            "password",
+            String::from("pass\u{200B}word").strip_zero_width_keep_emoji()
+        );
+        assert_eq!(
+            "abcde",
+            String::from("\u{FEFF}a\u{200B}b\u{200C}c\u{200D}d\u{2060}e")
+                .strip_zero_width_keep_emoji()
+        );
+        let family = String::from("👨\u{200D}👩\u{200D}👧");
+        assert_eq!(family, family.strip_zero_width_keep_emoji());
+        assert_eq!(
+            "❤\u{FE0F}\u{200D}🔥",
+            String::from("\u{200B}❤\u{FE0F}\u{200D}🔥").strip_zero_width_keep_emoji()
+        );
+        assert_eq!(
+            "a👍",
+            String::from("a\u{200D}👍\u{200D}").strip_zero_width_keep_emoji()
+        );
+        assert_eq!("", String::new().strip_zero_width_keep_emoji());
+    }
+
+    #[test]
+    fn test_has_mojibake() {
+        assert!(String::from("CafÃ© au lait").has_mojibake());
+        assert!(String::from("itâ€™s broken").has_mojibake());
+        assert!(String::from("Ã¼ber").has_mojibake());
+        assert!(!String::from("Café au lait").has_mojibake());
+        assert!(!String::from("it’s fine, naïve").has_mojibake());
+        assert!(!String::from("Ã alone").has_mojibake());
+        assert!(!String::from("שלום 汉字").has_mojibake());
+        assert!(!String::new().has_mojibake());
+    }
+
+    #[test]
+    fn test_fix_mojibake() {
+        assert_eq!("Café au lait", String::from("CafÃ© au lait").fix_mojibake());
+        assert_eq!(
+            "it’s “quoted”",
+            String::from("itâ€™s â€œquotedâ€\u{9D}").fix_mojibake()
+        );
+        assert_eq!("Größe", String::from("GrÃ¶ÃŸe").fix_mojibake());
+        assert_eq!("Café au lait", String::from("Café au lait").fix_mojibake());
+        assert_eq!("汉字 Ã©", String::from("汉字 Ã©").fix_mojibake());
+        assert_eq!("plain", String::from("plain").fix_mojibake());
+        assert_eq!("", String::new().fix_mojibake());
+    }
+
+    #[test]
+    fn test_size_report() {
+        let report = String::from("ab汉字e\u{301}\nx").size_report();
+        assert_eq!(13, report.bytes);
+        assert_eq!(8, report.chars);
+        #[cfg(feature = "unicode")]
+        assert_eq!(7, report.graphemes);
+        #[cfg(not(feature = "unicode"))]
+        assert_eq!(8, report.graphemes);
+        assert_eq!(9, report.display_width);
+        assert_eq!(2, report.lines);
+        assert_eq!(SizeReport::default(), String::new().size_report());
+        assert_eq!(1, String::from("one line\n").size_report().lines);
+    }
+
+    #[test]
+    fn test_truncate_bytes_ellipsis() {
+        let s = String::from("hello world");
+        assert_eq!("hello world", s.truncate_bytes_ellipsis(11));
+        assert_eq!("hello w…", s.truncate_bytes_ellipsis(10));
+        assert_eq!(10, s.truncate_bytes_ellipsis(10).len());
+        assert_eq!("…", s.truncate_bytes_ellipsis(3));
+        assert_eq!("he", s.truncate_bytes_ellipsis(2));
+        assert_eq!("", s.truncate_bytes_ellipsis(0));
+
+        let hebrew = String::from("שלום");
+        assert_eq!("ש…", hebrew.truncate_bytes_ellipsis(6));
+        assert_eq!("של…", hebrew.truncate_bytes_ellipsis(7));
+        assert_eq!("שלום", hebrew.truncate_bytes_ellipsis(8));
+        assert_eq!("ש", String::from("שלום").truncate_bytes_ellipsis(2));
+        assert_eq!("", String::from("汉字").truncate_bytes_ellipsis(2));
+    }
+
+    #[test]
+    fn test_pad() {
+        let s = String::from("abc");
+        assert_eq!("**abc*****", s.pad(2, 5, '*'));
+        assert_eq!("abc--", s.pad(0, 2, '-'));
+        assert_eq!("--abc", s.pad(2, 0, '-'));
+        assert_eq!("abc", s.pad(0, 0, '-'));
+        assert_eq!("  ", String::new().pad(1, 1, ' '));
+        assert_eq!("ששabcש", s.pad(2, 1, 'ש'));
+        assert_eq!("[abc]", s.pad(1, 0, '[').pad(0, 1, ']'));
+    }
+
+    #[test]
+    fn test_strip_numeric_suffix() {
+        assert_eq!(
+            (String::from("item"), Some(42)),
+            String::from("item42").strip_numeric_suffix()
+        );
+        assert_eq!(
+            (String::from("item"), None),
+            String::from("item").strip_numeric_suffix()
+        );
+        assert_eq!(
+            (String::from("v1.part-"), Some(7)),
+            String::from("v1.part-007").strip_numeric_suffix()
+        );
+        assert_eq!(
+            (String::new(), Some(2024)),
+            String::from("2024").strip_numeric_suffix()
+        );
+        assert_eq!(
+            (String::from("x"), Some(u64::MAX)),
+            String::from("x18446744073709551615").strip_numeric_suffix()
+        );
+        assert_eq!(
+            (String::from("id99999999999999999999"), None),
+            String::from("id99999999999999999999").strip_numeric_suffix()
+        );
+        assert_eq!((String::new(), None), String::new().strip_numeric_suffix());
+    }
+
+    #[test]
+    fn test_increment() {
+        assert_eq!("file2", String::from("file").increment());
+        assert_eq!("file3", String::from("file2").increment());
+        assert_eq!("file10", String::from("file9").increment());
+        assert_eq!("img010", String::from("img009").increment());
+        assert_eq!("img100", String::from("img099").increment());
+        assert_eq!("img1000", String::from("img999").increment());
+        assert_eq!("1", String::from("0").increment());
+        assert_eq!("2", String::new().increment());
+        assert_eq!(
+            "id100000000000000000000",
+            String::from("id99999999999999999999").increment()
+        );
+        let (base, n) = String::from("copy41").increment().strip_numeric_suffix();
+        assert_eq!(("copy", Some(42)), (base.as_str(), n));
+    }
+
+    #[test]
+    fn test_split_on_any_keep() {
+        let operators = ['+', '-', '*', '/', '(', ')'];
+        assert_eq!(
+            vec!["a", "+", "b", "*", "c"],
+            String::from("a+b*c").split_on_any_keep(&operators)
+        );
+        assert_eq!(
+            vec!["(", "x1", "-", "y", ")", "/", "2"],
+            String::from("(x1-y)/2").split_on_any_keep(&operators)
+        );
+        assert_eq!(
+            vec!["לא", ",", "כן"],
+            String::from("לא,כן").split_on_any_keep(&[','])
+        );
+        assert_eq!(
+            vec!["abc"],
+            String::from("abc").split_on_any_keep(&operators)
+        );
+        assert_eq!(vec!["abc"], String::from("abc").split_on_any_keep(&[]));
+        assert!(String::new().split_on_any_keep(&operators).is_empty());
+    }
+
+    #[test]
+    fn test_has_balanced_quotes() {
+        assert!(String::from("key = \"value\"").has_balanced_quotes());
+        assert!(String::from("'a' and \"b\"").has_balanced_quotes());
+        assert!(String::from("\"it's\" 'say \"hi\"'").has_balanced_quotes());
+        assert!(String::from("'a\\'b'").has_balanced_quotes());
+        assert!(String::from("\"C:\\\\\"").has_balanced_quotes());
+        assert!(String::from("no quotes").has_balanced_quotes());
+        assert!(String::new().has_balanced_quotes());
+        assert!(!String::from("key = \"value").has_balanced_quotes());
+        assert!(!String::from("'a\\'").has_balanced_quotes());
+        assert!(!String::from("\"a\" 'b").has_balanced_quotes());
+        assert!(!String::from("\"trailing\\").has_balanced_quotes());
+    }
+
+    #[test]
+    fn test_word_wrap_hyphenate() {
+        assert_eq!(
+            "see\nsuperca-\nlifragi-\nlistic\nnow",
+            String::from("see supercalifragilistic now").word_wrap_hyphenate(8)
+        );
+        assert_eq!(
+            "the quick\nbrown fox",
+            String::from("the quick brown fox").word_wrap_hyphenate(10)
+        );
+        assert_eq!(
+            "abcd-\nefgh-\nij ok",
+            String::from("abcdefghij ok").word_wrap_hyphenate(5)
+        );
+        assert_eq!("exact", String::from("exact").word_wrap_hyphenate(5));
+        assert_eq!("של-\nום", String::from("שלום").word_wrap_hyphenate(3));
+        assert_eq!("abc", String::from("abc").word_wrap_hyphenate(1));
+        assert_eq!(
+            "汉字-\n汉字",
+            String::from("汉字汉字").word_wrap_hyphenate(5)
+        );
+        assert_eq!("汉-\n字", String::from("汉字").word_wrap_hyphenate(2));
+        assert_eq!("", String::new().word_wrap_hyphenate(4));
+    }
+
+    #[test]
+    fn test_capitalize_sentences() {
+        assert_eq!(
+            "The NASA launch is today. Is it on TV? Yes!",
+            String::from("the NASA launch is today. is it on TV? yes!").capitalize_sentences()
+        );
+        assert_eq!(
+            "Pi is 3.14 exactly... Not really.",
+            String::from("pi is 3.14 exactly... not really.").capitalize_sentences()
+        );
+        assert_eq!(
+            "Done.\nNext line",
+            String::from("done.\nnext line").capitalize_sentences()
+        );
+        assert_eq!("  Hello", String::from("  hello").capitalize_sentences());
+        assert_eq!(
+            "ŁÓDŹ. Éclair",
+            String::from("ŁÓDŹ. éclair").capitalize_sentences()
+        );
+        assert_eq!("", String::new().capitalize_sentences());
+    }
+
+    #[test]
+    fn test_squeeze_punctuation() {
+        assert_eq!(
+            "Wow! Really? Yes!?",
+            String::from("Wow!!!! Really??? Yes!!??").squeeze_punctuation()
+        );
+        assert_eq!(
+            "aaa 111 ...",
+            String::from("aaa 111 ...").squeeze_punctuation_to(3)
+        );
+        assert_eq!(
+            "wait...",
+            String::from("wait......").squeeze_punctuation_to(3)
+        );
+        assert_eq!("no!!", String::from("no!!!!!").squeeze_punctuation_to(2));
+        assert_eq!("no!", String::from("no!!!!!").squeeze_punctuation_to(0));
+        assert_eq!("好。", String::from("好。。。").squeeze_punctuation());
+        assert_eq!("a.b.c", String::from("a.b.c").squeeze_punctuation());
+        assert_eq!("", String::new().squeeze_punctuation());
+    }
+
+    #[test]
+    fn test_truncate_html() {
+        let html = String::from("<p>Hello <b>bold world</b> again</p>");
+        assert_eq!("<p>Hello <b>bo</b></p>", html.truncate_html(8));
+        assert_eq!("<p>Hello <b>bold world</b></p>", html.truncate_html(16));
+        assert_eq!("<p>Hello </p>", html.truncate_html(6));
+        assert_eq!(html, html.truncate_html(100));
+        assert_eq!("", html.truncate_html(0));
+        assert_eq!(
+            "a&amp;b<br>c",
+            String::from("a&amp;b<br>cdef").truncate_html(4)
+        );
+        assert_eq!(
+            "<i>x <img src=\"a.png\"/>y</i>",
+            String::from("<i>x <img src=\"a.png\"/>yz</i>").truncate_html(3)
+        );
+        assert_eq!("1 < 2", String::from("1 < 2 <em>ok</em>").truncate_html(5));
+        let misnested = String::from("<b><i>abc</b>def");
+        assert_eq!("<b><i>abc</i></b>d", misnested.truncate_html(4));
+        assert_eq!(
+            "<p><b><i>ab</i></b></p>c",
+            String::from("<p><b><i>ab</p>cd").truncate_html(3)
+        );
+        assert_eq!("", String::new().truncate_html(5));
+    }
+
+    #[test]
+    fn test_visible_text_len() {
+        assert_eq!(
+            11,
+            String::from("<p>Hello <b>world</b></p>").visible_text_len()
+        );
+        assert_eq!(5, String::from("a &amp; b<br/>").visible_text_len());
+        assert_eq!(3, String::from("&lt;&#65;&#x42;").visible_text_len());
+        assert_eq!(7, String::from("&bogus;").visible_text_len());
+        assert_eq!(5, String::from("1 < 2<!-- note -->").visible_text_len());
+        assert_eq!(4, String::from("<i>שלום</i>").visible_text_len());
+        let html = String::from("<p>Hello <b>bold world</b> again</p>");
+        assert_eq!(8, html.truncate_html(8).visible_text_len());
+        assert_eq!(0, String::new().visible_text_len());
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        let html = String::from(
+            "<h1>Title</h1>\n<p>First   paragraph with a <a href=\"https://example.com\">link</a>.</p>\n\
+             <p>Second&nbsp;one &amp; more<br>on two lines</p>",
+        );
+        assert_eq!(
+            "Title\n\nFirst paragraph with a link.\n\nSecond one & more\non two lines",
+            html.html_to_text()
+        );
+        assert_eq!(
+            "one\ntwo",
+            String::from("<ul><li>one</li><li>two</li></ul>").html_to_text()
+        );
+        assert_eq!("a\n\nb", String::from("a<br><br>b<br>").html_to_text());
+        assert_eq!(
+            "Hi there",
+            String::from(
+                "<style>p { color: red; }</style><div>Hi <b>there</b></div><script>x()</script>"
+            )
+            .html_to_text()
+        );
+        assert_eq!("1 < 2", String::from("<div>1 &lt; 2</div>").html_to_text());
+        assert_eq!("", String::new().html_to_text());
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(3, String::from("kitten").levenshtein("sitting"));
+        assert_eq!(2, String::from("flaw").levenshtein("lawn"));
+        assert_eq!(0, String::from("same").levenshtein("same"));
+        assert_eq!(4, String::new().levenshtein("four"));
+        assert_eq!(4, String::from("four").levenshtein(""));
+        assert_eq!(1, String::from("שלום").levenshtein("שלם"));
+    }
+
+    #[test]
+    fn test_levenshtein_within() {
+        assert_eq!(
+            Some(3),
+            String::from("kitten").levenshtein_within("sitting", 3)
+        );
+        assert_eq!(
+            None,
+            String::from("kitten").levenshtein_within("sitting", 2)
+        );
+        assert_eq!(Some(0), String::from("abc").levenshtein_within("abc", 0));
+        assert_eq!(None, String::from("abc").levenshtein_within("abcdef", 2));
+        assert_eq!(Some(0), String::new().levenshtein_within("", 0));
+        assert_eq!(None, String::from("a").levenshtein_within("", 0));
+
+        let words = [
+            "",
+            "a",
+            "ab",
+            "abc",
+            "acb",
+            "kitten",
+            "sitting",
+            "saturday",
+            "sunday",
+            "rosettacode",
+            "raisethysword",
+            "שלום",
+            "שלם",
+            "abcdefghij",
+            "jihgfedcba",
+        ];
+        for a in words.iter() {
+            for b in words.iter() {
+                let distance = String::from(*a).levenshtein(b);
+                for max in 0..12 {
+                    let expected = if distance <= max {
+                        Some(distance)
+                    } else {
+                        None
+                    };
+                    assert_eq!(
+                        expected,
+                        String::from(*a).levenshtein_within(b, max),
+                        "{} / {} within {}",
+                        a,
+                        b,
+                        max
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_damerau_levenshtein() {
+        assert_eq!(1, String::from("ca").damerau_levenshtein("ac"));
+        assert_eq!(2, String::from("ca").levenshtein("ac"));
+        assert_eq!(1, String::from("teh").damerau_levenshtein("the"));
+        assert_eq!(2, String::from("teh").levenshtein("the"));
+        assert_eq!(3, String::from("ca").damerau_levenshtein("abc"));
+        assert_eq!(3, String::from("kitten").damerau_levenshtein("sitting"));
+        assert_eq!(1, String::from("שלום").damerau_levenshtein("שלוםם"));
+        assert_eq!(1, String::from("אב").damerau_levenshtein("בא"));
+        assert_eq!(0, String::new().damerau_levenshtein(""));
+        assert_eq!(3, String::new().damerau_levenshtein("abc"));
+    }
+
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!("[----------]", progress_bar(0.0, 10));
+        assert_eq!("[#####-----]", progress_bar(0.5, 10));
+        assert_eq!("[##########]", progress_bar(1.0, 10));
+        assert_eq!("[###-]", progress_bar(0.7, 4));
+        assert_eq!("[----]", progress_bar(-0.5, 4));
+        assert_eq!("[####]", progress_bar(2.0, 4));
+        assert_eq!("[]", progress_bar(0.5, 0));
+    }
+
+    #[test]
+    fn test_progress_bar_smooth() {
+        assert_eq!("[          ]", progress_bar_smooth(0.0, 10));
+        assert_eq!("[█████     ]", progress_bar_smooth(0.5, 10));
+        assert_eq!("[██████████]", progress_bar_smooth(1.0, 10));
+        assert_eq!("[███▌      ]", progress_bar_smooth(0.35, 10));
+        assert_eq!("[▏   ]", progress_bar_smooth(0.03, 4));
+        assert_eq!(6, progress_bar_smooth(0.41, 4).chars().count());
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(
+            "▁▂▃▄▅▆▇█",
+            sparkline(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0])
+        );
+        assert_eq!("▁█▁", sparkline(&[-10.0, 10.0, -10.0]));
+        assert_eq!("▁▅█", sparkline(&[0.0, 0.6, 1.0]));
+        assert_eq!("▄▄▄", sparkline(&[3.5, 3.5, 3.5]));
+        assert_eq!("▄", sparkline(&[42.0]));
+        assert_eq!("", sparkline(&[]));
+    }
+
+    #[test]
+    fn test_markdown_table() {
+        let rows = vec![
+            vec![String::from("ext-string"), String::from("0.1.5")],
+            vec![String::from("a|b")],
+        ];
+        assert_eq!(
+            "| Name       | Version |\n\
+             | ---------- | ------- |\n\
+             | ext-string | 0.1.5   |\n\
+             | a\\|b       |         |\n",
+            markdown_table(&["Name", "Version"], &rows)
+        );
+        assert_eq!(
+            "| 名前 |     |\n| ---- | --- |\n| 汉   | y   |\n",
+            markdown_table(&["名前"], &[vec![String::from("汉"), String::from("y")]])
+        );
+        assert_eq!("| a   |\n| --- |\n", markdown_table(&["a"], &[]));
+    }
+
+    #[test]
+    fn test_align_decimal() {
+        assert_eq!("  3.500", String::from("3.5").align_decimal(3, 3));
+        assert_eq!(" 10.000", String::from("10").align_decimal(3, 3));
+        assert_eq!("  0.125", String::from("0.125").align_decimal(3, 3));
+        assert_eq!(" -2.250", String::from("-2.25").align_decimal(3, 3));
+        assert_eq!("12345.6789", String::from("12345.6789").align_decimal(3, 3));
+        assert_eq!("   .5", String::from(".5").align_decimal(3, 1));
+        assert_eq!(" 42", String::from("42").align_decimal(3, 0));
+        assert_eq!("abc", String::from("abc").align_decimal(3, 3));
+        assert_eq!("1.2.3", String::from("1.2.3").align_decimal(3, 3));
+        assert_eq!("-", String::from("-").align_decimal(3, 3));
+        assert_eq!("", String::new().align_decimal(3, 3));
+    }
+
+    #[test]
+    fn test_dedent_min() {
+        let code =
+            String::from("    fn main() {\n        if x {\n            y();\n        }\n\n    }\n");
+        assert_eq!(
+            "fn main() {\n    if x {\n        y();\n    }\n\n}\n",
+            code.dedent_min()
+        );
+        assert_eq!(
+            "a\n  b\r\n",
+            String::from("    a\n      b\r\n").dedent_min()
+        );
+        assert_eq!(
+            "one\n    \n  two",
+            String::from("  one\n      \n    two").dedent_min()
+        );
+        assert_eq!("\tx\n y", String::from(" \tx\n  y").dedent_min());
+        assert_eq!("flush\n  kept", String::from("flush\n  kept").dedent_min());
+        assert_eq!(
+            "\u{2003}a\n\u{2002}b",
+            String::from("\u{2003}a\n\u{2002}b").dedent_min()
+        );
+        assert_eq!(
+            "a\n\u{2002}\n",
+            String::from(" \u{2003}a\n \u{2002}\n").dedent_min()
+        );
+        assert_eq!(
+            "a\n\u{3000}b",
+            String::from("\u{3000}a\n\u{3000}\u{3000}b").dedent_min()
+        );
+        assert_eq!("   ", String::from("   ").dedent_min());
+        assert_eq!("", String::new().dedent_min());
     }
 }